@@ -0,0 +1,1111 @@
+// Runtime support of the `ShortDebug` derive, re-exported by `short_debug`
+
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque};
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cell::OnceCell;
+use core::fmt::Debug;
+use core::ops::Deref;
+use core::pin::Pin;
+
+// Values that `ShortDebug` skips when they are empty.
+// Recognized collections use it automatically, own types can implement it
+// and mark fields with `#[debug(collection)]`
+pub trait ShortEmpty {
+	fn is_short_empty(&self) -> bool;
+}
+
+// Type-based shortening of `#[debug(auto)]` fields, that also works through generics
+// and type aliases. Implemented for `Option`, collections, primitives, common std types
+// and types deriving `ShortDebug`. Generic `auto` fields need it from their type arguments,
+// other `Debug` types can implement it with `Inner = Self` to be always printed
+pub trait ShortValue {
+	type Inner: Debug + ?Sized;
+
+	// Value to print in place of `self`, or `None` to skip the field
+	fn short_value(&self) -> Option<&Self::Inner>;
+
+	// Value to print in place of `self` as an element of `#[debug(elements)]` collections,
+	// or `None` for `None`. Only options are unwrapped, empty values are printed as they are
+	fn short_element(&self) -> Option<&dyn Debug>
+	where
+		Self: Debug + Sized,
+	{
+		Some(self)
+	}
+}
+
+// Implements `ShortEmpty` with the inherent `is_empty`,
+// and `ShortValue` that skips empty values
+macro_rules! impl_short_empty {
+	($($(#[$attr:meta])* [$($generics:tt)*] $ty:ty,)*) => {$(
+		$(#[$attr])*
+		impl<$($generics)*> ShortEmpty for $ty {
+			fn is_short_empty(&self) -> bool {
+				self.is_empty()
+			}
+		}
+
+		$(#[$attr])*
+		impl<$($generics)*> ShortValue for $ty
+		where
+			Self: Debug,
+		{
+			type Inner = Self;
+
+			fn short_value(&self) -> Option<&Self> {
+				(!self.is_empty()).then_some(self)
+			}
+		}
+	)*};
+}
+
+impl_short_empty! {
+	[] str,
+	[] String,
+	[T] [T],
+	[T] Vec<T>,
+	[T] VecDeque<T>,
+	[T] LinkedList<T>,
+	[T] BinaryHeap<T>,
+	[K, V] BTreeMap<K, V>,
+	[T] BTreeSet<T>,
+	#[cfg(feature = "std")]
+	[K, V, S] std::collections::HashMap<K, V, S>,
+	#[cfg(feature = "std")]
+	[T, S] std::collections::HashSet<T, S>,
+	#[cfg(feature = "smallvec")]
+	[A: smallvec::Array] smallvec::SmallVec<A>,
+	#[cfg(feature = "arrayvec")]
+	[T, const CAP: usize] arrayvec::ArrayVec<T, CAP>,
+	#[cfg(feature = "arrayvec")]
+	[const CAP: usize] arrayvec::ArrayString<CAP>,
+	#[cfg(feature = "indexmap")]
+	[K, V, S] indexmap::IndexMap<K, V, S>,
+	#[cfg(feature = "indexmap")]
+	[T, S] indexmap::IndexSet<T, S>,
+	#[cfg(feature = "hashbrown")]
+	[K, V, S] hashbrown::HashMap<K, V, S>,
+	#[cfg(feature = "hashbrown")]
+	[T, S] hashbrown::HashSet<T, S>,
+	#[cfg(feature = "bytes")]
+	[] bytes::Bytes,
+	#[cfg(feature = "bytes")]
+	[] bytes::BytesMut,
+	#[cfg(feature = "heapless")]
+	[T, const N: usize, L: heapless::LenType] heapless::Vec<T, N, L>,
+}
+
+// Arrays are only empty with zero length
+impl<T, const N: usize> ShortEmpty for [T; N] {
+	fn is_short_empty(&self) -> bool {
+		N == 0
+	}
+}
+
+impl<T: Debug, const N: usize> ShortValue for [T; N] {
+	type Inner = Self;
+
+	fn short_value(&self) -> Option<&Self> {
+		(N != 0).then_some(self)
+	}
+}
+
+impl<T> ShortEmpty for Option<T> {
+	fn is_short_empty(&self) -> bool {
+		self.is_none()
+	}
+}
+
+// Nested options and options of empty values are skipped like `None`
+impl<T: ShortValue + Debug> ShortValue for Option<T> {
+	type Inner = T::Inner;
+
+	fn short_value(&self) -> Option<&T::Inner> {
+		self.as_ref()?.short_value()
+	}
+
+	fn short_element(&self) -> Option<&dyn Debug> {
+		self.as_ref()?.short_element()
+	}
+}
+
+// Values that are always printed as is
+macro_rules! impl_short_value_always {
+	($($(#[$attr:meta])* $ty:ty),* $(,)?) => {$(
+		$(#[$attr])*
+		impl ShortValue for $ty {
+			type Inner = Self;
+
+			fn short_value(&self) -> Option<&Self> {
+				Some(self)
+			}
+		}
+	)*};
+}
+
+impl_short_value_always!(
+	u8,
+	u16,
+	u32,
+	u64,
+	u128,
+	usize,
+	i8,
+	i16,
+	i32,
+	i64,
+	i128,
+	isize,
+	f32,
+	f64,
+	bool,
+	char,
+	(),
+	core::cmp::Ordering,
+	core::time::Duration,
+	core::num::NonZeroU8,
+	core::num::NonZeroU16,
+	core::num::NonZeroU32,
+	core::num::NonZeroU64,
+	core::num::NonZeroU128,
+	core::num::NonZeroUsize,
+	core::num::NonZeroI8,
+	core::num::NonZeroI16,
+	core::num::NonZeroI32,
+	core::num::NonZeroI64,
+	core::num::NonZeroI128,
+	core::num::NonZeroIsize,
+	#[cfg(feature = "std")]
+	std::time::Instant,
+	#[cfg(feature = "std")]
+	std::time::SystemTime,
+	#[cfg(feature = "std")]
+	std::net::IpAddr,
+	#[cfg(feature = "std")]
+	std::net::Ipv4Addr,
+	#[cfg(feature = "std")]
+	std::net::Ipv6Addr,
+	#[cfg(feature = "std")]
+	std::net::SocketAddr,
+	#[cfg(feature = "std")]
+	std::net::SocketAddrV4,
+	#[cfg(feature = "std")]
+	std::net::SocketAddrV6,
+	#[cfg(feature = "std")]
+	std::thread::ThreadId,
+);
+
+impl<T: ?Sized> ShortValue for core::marker::PhantomData<T> {
+	type Inner = Self;
+
+	fn short_value(&self) -> Option<&Self> {
+		Some(self)
+	}
+}
+
+// Tuples are always printed, their elements as is
+macro_rules! impl_short_value_tuple {
+	($(($($name:ident),+))*) => {$(
+		impl<$($name: Debug),+> ShortValue for ($($name,)+) {
+			type Inner = Self;
+
+			fn short_value(&self) -> Option<&Self> {
+				Some(self)
+			}
+		}
+	)*};
+}
+
+impl_short_value_tuple! {
+	(A)
+	(A, B)
+	(A, B, C)
+	(A, B, C, D)
+	(A, B, C, D, E)
+	(A, B, C, D, E, F)
+	(A, B, C, D, E, F, G)
+	(A, B, C, D, E, F, G, H)
+	(A, B, C, D, E, F, G, H, I)
+	(A, B, C, D, E, F, G, H, I, J)
+	(A, B, C, D, E, F, G, H, I, J, K)
+	(A, B, C, D, E, F, G, H, I, J, K, L)
+}
+
+// Paths are skipped when empty, like strings
+#[cfg(feature = "std")]
+impl ShortValue for std::ffi::OsStr {
+	type Inner = Self;
+
+	fn short_value(&self) -> Option<&Self> {
+		(!self.is_empty()).then_some(self)
+	}
+}
+
+#[cfg(feature = "std")]
+impl ShortValue for std::ffi::OsString {
+	type Inner = Self;
+
+	fn short_value(&self) -> Option<&Self> {
+		(!self.is_empty()).then_some(self)
+	}
+}
+
+#[cfg(feature = "std")]
+impl ShortValue for std::path::Path {
+	type Inner = Self;
+
+	fn short_value(&self) -> Option<&Self> {
+		(!self.as_os_str().is_empty()).then_some(self)
+	}
+}
+
+#[cfg(feature = "std")]
+impl ShortValue for std::path::PathBuf {
+	type Inner = Self;
+
+	fn short_value(&self) -> Option<&Self> {
+		(!self.as_os_str().is_empty()).then_some(self)
+	}
+}
+
+// Pointers are empty when the pointee is, and print the pointee
+macro_rules! impl_short_deref {
+	($($ty:ty),*) => {$(
+		impl<T: ShortEmpty + ?Sized> ShortEmpty for $ty {
+			fn is_short_empty(&self) -> bool {
+				(**self).is_short_empty()
+			}
+		}
+
+		impl<T: ShortValue + ?Sized> ShortValue for $ty {
+			type Inner = T::Inner;
+
+			fn short_value(&self) -> Option<&T::Inner> {
+				(**self).short_value()
+			}
+		}
+	)*};
+}
+
+impl_short_deref!(&T, &mut T, Box<T>, Rc<T>, Arc<T>);
+
+impl<B: ShortEmpty + ToOwned + ?Sized> ShortEmpty for Cow<'_, B> {
+	fn is_short_empty(&self) -> bool {
+		(**self).is_short_empty()
+	}
+}
+
+impl<B: ShortValue + ToOwned + ?Sized> ShortValue for Cow<'_, B> {
+	type Inner = B::Inner;
+
+	fn short_value(&self) -> Option<&B::Inner> {
+		(**self).short_value()
+	}
+}
+
+impl<P: Deref<Target: ShortEmpty>> ShortEmpty for Pin<P> {
+	fn is_short_empty(&self) -> bool {
+		(**self).is_short_empty()
+	}
+}
+
+impl<P: Deref<Target: ShortValue>> ShortValue for Pin<P> {
+	type Inner = <P::Target as ShortValue>::Inner;
+
+	fn short_value(&self) -> Option<&Self::Inner> {
+		(**self).short_value()
+	}
+}
+
+// Once cells print their value once it's initialized
+impl<T: Debug> ShortValue for OnceCell<T> {
+	type Inner = T;
+
+	fn short_value(&self) -> Option<&T> {
+		self.get()
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T: Debug> ShortValue for std::sync::OnceLock<T> {
+	type Inner = T;
+
+	fn short_value(&self) -> Option<&T> {
+		self.get()
+	}
+}
+
+// Used by the generated code
+#[doc(hidden)]
+pub mod __private {
+	use super::{ShortEmpty, ShortValue};
+	use alloc::string::String;
+	use core::cell::{Cell, OnceCell, Ref, RefCell};
+	use core::fmt::{self, Debug, Display, Formatter, Write};
+	use core::ops::Deref;
+
+	// Autoref specialization for `#[debug(auto)]` fields of concrete types:
+	// `(&&&Auto(value)).short_value()` resolves to `ViaShortValue` when the type
+	// implements `ShortValue`, to `ViaOptionDebug` for options of other types
+	// and falls back to `ViaDebug` otherwise.
+	// Generic fields get a `ShortValue` bound instead, as the choice is made
+	// before the type is known
+	pub struct Auto<'a, T: ?Sized>(pub &'a T);
+
+	impl<T: ?Sized> Clone for Auto<'_, T> {
+		fn clone(&self) -> Self {
+			*self
+		}
+	}
+
+	impl<T: ?Sized> Copy for Auto<'_, T> {}
+
+	// Methods take `self` so the result borrows the value and not the temporary `Auto`,
+	// which lets element closures of `#[debug(elements)]` return it
+	pub trait ViaShortValue<'a> {
+		type Inner: Debug + ?Sized + 'a;
+
+		fn short_value(self) -> Option<&'a Self::Inner>;
+	}
+
+	impl<'a, T: ShortValue + ?Sized> ViaShortValue<'a> for &&Auto<'a, T> {
+		type Inner = T::Inner;
+
+		fn short_value(self) -> Option<&'a T::Inner> {
+			self.0.short_value()
+		}
+	}
+
+	pub trait ViaOptionDebug<'a> {
+		type Inner: Debug + ?Sized + 'a;
+
+		fn short_value(self) -> Option<&'a Self::Inner>;
+	}
+
+	impl<'a, T: Debug> ViaOptionDebug<'a> for &Auto<'a, Option<T>> {
+		type Inner = T;
+
+		fn short_value(self) -> Option<&'a T> {
+			self.0.as_ref()
+		}
+	}
+
+	pub trait ViaDebug<'a> {
+		type Inner: Debug + ?Sized + 'a;
+
+		fn short_value(self) -> Option<&'a Self::Inner>;
+	}
+
+	impl<'a, T: Debug + ?Sized> ViaDebug<'a> for Auto<'a, T> {
+		type Inner = T;
+
+		fn short_value(self) -> Option<&'a T> {
+			Some(self.0)
+		}
+	}
+
+	// Elements of `#[debug(elements)]` collections: `(&&&Auto(element)).short_element()`
+	// unwraps nested options of `ShortValue` types, a single option of other types,
+	// and keeps other elements as they are
+	pub trait ViaShortElement<'a> {
+		fn short_element(self) -> Option<&'a dyn Debug>;
+	}
+
+	impl<'a, T: ShortValue + Debug> ViaShortElement<'a> for &&Auto<'a, T> {
+		fn short_element(self) -> Option<&'a dyn Debug> {
+			self.0.short_element()
+		}
+	}
+
+	pub trait ViaOptionElement<'a> {
+		fn short_element(self) -> Option<&'a dyn Debug>;
+	}
+
+	impl<'a, T: Debug> ViaOptionElement<'a> for &Auto<'a, Option<T>> {
+		fn short_element(self) -> Option<&'a dyn Debug> {
+			self.0.as_ref().map(|value| value as &dyn Debug)
+		}
+	}
+
+	pub trait ViaDebugElement<'a> {
+		fn short_element(self) -> Option<&'a dyn Debug>;
+	}
+
+	impl<'a, T: Debug> ViaDebugElement<'a> for Auto<'a, T> {
+		fn short_element(self) -> Option<&'a dyn Debug> {
+			Some(self.0)
+		}
+	}
+
+	// Text kept by `redact(last = N)`: `(&&Auto(value)).redact_text()` is the `Display` text
+	// when the type implements it, and the `Debug` text otherwise
+	pub trait ViaDisplay<'a> {
+		type Text: Display;
+
+		fn redact_text(self) -> Self::Text;
+	}
+
+	impl<'a, T: Display + ?Sized> ViaDisplay<'a> for &Auto<'a, T> {
+		type Text = &'a T;
+
+		fn redact_text(self) -> &'a T {
+			self.0
+		}
+	}
+
+	pub trait ViaDebugText<'a> {
+		type Text: Display;
+
+		fn redact_text(self) -> Self::Text;
+	}
+
+	impl<'a, T: Debug + ?Sized> ViaDebugText<'a> for Auto<'a, T> {
+		type Text = DebugText<'a, T>;
+
+		fn redact_text(self) -> DebugText<'a, T> {
+			DebugText(self.0)
+		}
+	}
+
+	pub struct DebugText<'a, T: ?Sized>(&'a T);
+
+	impl<T: Debug + ?Sized> Display for DebugText<'_, T> {
+		fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+			self.0.fmt(fmt)
+		}
+	}
+
+	// The same dispatch for recognized collections: `(&&Auto(value)).short_empty()` uses
+	// `ShortEmpty` when the type implements it. Types that are only named like a known
+	// collection, such as `hashbrown::HashMap` without the cargo feature, are never empty
+	pub trait ViaShortEmpty {
+		fn short_empty(self) -> bool;
+	}
+
+	impl<T: ShortEmpty + ?Sized> ViaShortEmpty for &Auto<'_, T> {
+		fn short_empty(self) -> bool {
+			self.0.is_short_empty()
+		}
+	}
+
+	pub trait ViaNonEmpty {
+		fn short_empty(self) -> bool;
+	}
+
+	impl<T: ?Sized> ViaNonEmpty for Auto<'_, T> {
+		fn short_empty(self) -> bool {
+			false
+		}
+	}
+
+	// `debug_struct` that can also print without the name for `#[debug(anonymous)]`
+	// as `{ a: 1 }`, and end with a count of skipped fields for `#[debug(count_skipped)]`
+	pub struct DebugStruct<'a, 'b> {
+		fields: Fields<'a, 'b>,
+	}
+
+	impl<'a, 'b> DebugStruct<'a, 'b> {
+		pub fn new(fmt: &'a mut Formatter<'b>, name: &'a str) -> Self {
+			Self { fields: Fields::new(fmt, name, false) }
+		}
+
+		pub fn field(&mut self, name: &str, value: &dyn Debug) -> &mut Self {
+			match self.fields.fmt.alternate() {
+				true => self.fields.entry(format_args!("{name}: {value:#?}"), true),
+				false => self.fields.entry(format_args!("{name}: {value:?}"), true),
+			}
+			self
+		}
+
+		pub fn finish(&mut self) -> fmt::Result {
+			self.fields.finish()
+		}
+
+		pub fn finish_non_exhaustive(&mut self) -> fmt::Result {
+			self.fields.entry(format_args!(".."), false);
+			self.fields.finish()
+		}
+
+		pub fn finish_hidden(&mut self, hidden: usize) -> fmt::Result {
+			self.fields.finish_hidden(hidden)
+		}
+	}
+
+	// `debug_tuple` that can end with a count of skipped fields for `#[debug(count_skipped)]`
+	pub struct DebugTuple<'a, 'b> {
+		fields: Fields<'a, 'b>,
+	}
+
+	impl<'a, 'b> DebugTuple<'a, 'b> {
+		pub fn new(fmt: &'a mut Formatter<'b>, name: &'a str) -> Self {
+			Self { fields: Fields::new(fmt, name, true) }
+		}
+
+		pub fn field(&mut self, value: &dyn Debug) -> &mut Self {
+			match self.fields.fmt.alternate() {
+				true => self.fields.entry(format_args!("{value:#?}"), true),
+				false => self.fields.entry(format_args!("{value:?}"), true),
+			}
+			self
+		}
+
+		pub fn finish(&mut self) -> fmt::Result {
+			self.fields.finish()
+		}
+
+		pub fn finish_hidden(&mut self, hidden: usize) -> fmt::Result {
+			self.fields.finish_hidden(hidden)
+		}
+	}
+
+	// Output shared by `DebugStruct` and `DebugTuple`
+	struct Fields<'a, 'b> {
+		fmt: &'a mut Formatter<'b>,
+		// Empty for anonymous structs
+		name: &'a str,
+		tuple: bool,
+		result: fmt::Result,
+		has_fields: bool,
+	}
+
+	impl<'a, 'b> Fields<'a, 'b> {
+		fn new(fmt: &'a mut Formatter<'b>, name: &'a str, tuple: bool) -> Self {
+			Self { fmt, name, tuple, result: Ok(()), has_fields: false }
+		}
+
+		fn finish(&mut self) -> fmt::Result {
+			self.result.and_then(|()| match (self.has_fields, self.tuple, self.fmt.alternate()) {
+				(false, _, _) if !self.name.is_empty() => self.fmt.write_str(self.name),
+				(false, true, _) => self.fmt.write_str("()"),
+				(false, false, _) => self.fmt.write_str("{}"),
+				(true, true, _) => self.fmt.write_str(")"),
+				(true, false, true) => self.fmt.write_str("}"),
+				(true, false, false) => self.fmt.write_str(" }"),
+			})
+		}
+
+		// Ends with `..N hidden`, or as usual when nothing is hidden
+		fn finish_hidden(&mut self, hidden: usize) -> fmt::Result {
+			if hidden > 0 {
+				self.entry(format_args!("..{hidden} hidden"), false);
+			}
+			self.finish()
+		}
+
+		// Alternate output puts a comma after every field, but not after the last `..` entry
+		fn entry(&mut self, entry: fmt::Arguments, comma: bool) {
+			self.result = self.result.and_then(|()| {
+				let alternate = self.fmt.alternate();
+				if !self.has_fields {
+					self.fmt.write_str(self.name)?;
+					let open = match (self.tuple, alternate) {
+						(true, true) => "(\n",
+						(true, false) => "(",
+						(false, true) => "{\n",
+						(false, false) => "{ ",
+					};
+					if !self.tuple && !self.name.is_empty() {
+						self.fmt.write_str(" ")?;
+					}
+					self.fmt.write_str(open)?;
+				}
+				else if !alternate {
+					self.fmt.write_str(", ")?;
+				}
+				match alternate {
+					true => {
+						let mut pad = PadAdapter { fmt: self.fmt, on_newline: true };
+						writeln!(pad, "{entry}{}", if comma { "," } else { "" })
+					}
+					false => write!(self.fmt, "{entry}"),
+				}
+			});
+			self.has_fields = true;
+		}
+	}
+
+	// Indents the fields of alternate `{:#?}` output, like std builders do
+	struct PadAdapter<'a, 'b> {
+		fmt: &'a mut Formatter<'b>,
+		on_newline: bool,
+	}
+
+	impl fmt::Write for PadAdapter<'_, '_> {
+		fn write_str(&mut self, s: &str) -> fmt::Result {
+			for line in s.split_inclusive('\n') {
+				if self.on_newline {
+					self.fmt.write_str("    ")?;
+				}
+				self.on_newline = line.ends_with('\n');
+				self.fmt.write_str(line)?;
+			}
+			Ok(())
+		}
+	}
+
+	// Type name followed by the generic arguments for `#[debug(show_generics)]`,
+	// and the variant name of qualified enums
+	pub fn generic_name(type_name: &str, args: &[&dyn Display], suffix: &str) -> String {
+		let mut name = String::from(type_name);
+		if !args.is_empty() {
+			name.push('<');
+			for (index, arg) in args.iter().enumerate() {
+				if index > 0 {
+					name.push_str(", ");
+				}
+				let _ = write!(name, "{arg}");
+			}
+			name.push('>');
+		}
+		name.push_str(suffix);
+		name
+	}
+
+	// `core::any::type_name` without module paths, `Vec<app::User>` is printed as `Vec<User>`
+	pub struct ShortTypeName(pub &'static str);
+
+	impl Display for ShortTypeName {
+		fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+			let is_path = |c: char| c.is_alphanumeric() || c == '_' || c == ':';
+			let mut rest = self.0;
+			while !rest.is_empty() {
+				let path_end = rest.find(|c| !is_path(c)).unwrap_or(rest.len());
+				let (path, tail) = rest.split_at(path_end);
+				fmt.write_str(path.rsplit("::").next().unwrap_or(path))?;
+				let delimiters_end = tail.find(is_path).unwrap_or(tail.len());
+				let (delimiters, tail) = tail.split_at(delimiters_end);
+				fmt.write_str(delimiters)?;
+				rest = tail;
+			}
+			Ok(())
+		}
+	}
+
+	// Value of an option or a cell field, from `(&&Auto(value)).short_option()`,
+	// `short_once()` or `short_get()`
+	pub enum Unwrapped<'a, V> {
+		// The wrapped value, or a guard holding it
+		Value(V),
+		// `None`, an uninitialized cell, or the marker of a borrowed or locked cell
+		Missing(&'static str),
+		// Types that are only named like options and cells are printed as they are
+		Plain(&'a dyn Debug),
+	}
+
+	pub trait ViaOption<'a> {
+		type Value;
+
+		fn short_option(self) -> Unwrapped<'a, Self::Value>;
+	}
+
+	impl<'a, T> ViaOption<'a> for &Auto<'a, Option<T>> {
+		type Value = &'a T;
+
+		fn short_option(self) -> Unwrapped<'a, &'a T> {
+			short_option(self.0)
+		}
+	}
+
+	// Fields marked with `#[debug(option)]` are always options
+	pub fn short_option<T>(option: &Option<T>) -> Unwrapped<'_, &T> {
+		match option {
+			Some(value) => Unwrapped::Value(value),
+			None => Unwrapped::Missing("None"),
+		}
+	}
+
+	pub trait ViaShortOnce<'a> {
+		type Value;
+		type Wrapper: Debug;
+
+		fn short_once(self) -> Unwrapped<'a, Self::Value>;
+
+		fn wrapper(self, value: &'a dyn Debug) -> Self::Wrapper;
+	}
+
+	impl<'a, T: ShortOnce + ?Sized> ViaShortOnce<'a> for &Auto<'a, T> {
+		type Value = &'a T::Value;
+		type Wrapper = OnceWrapper<'a, T>;
+
+		fn short_once(self) -> Unwrapped<'a, &'a T::Value> {
+			match ShortOnce::short_once(self.0) {
+				Some(value) => Unwrapped::Value(value),
+				None => Unwrapped::Missing("<uninit>"),
+			}
+		}
+
+		fn wrapper(self, value: &'a dyn Debug) -> OnceWrapper<'a, T> {
+			OnceWrapper(self.0, value)
+		}
+	}
+
+	pub trait ViaShortCell<'a> {
+		type Value;
+		type Wrapper: Debug;
+
+		fn short_get(self) -> Unwrapped<'a, Self::Value>;
+
+		fn wrapper(self, value: &'a dyn Debug) -> Self::Wrapper;
+	}
+
+	impl<'a, T: ShortCell + ?Sized + 'a> ViaShortCell<'a> for &Auto<'a, T> {
+		type Value = T::Guard<'a>;
+		type Wrapper = CellWrapper<'a, T>;
+
+		fn short_get(self) -> Unwrapped<'a, T::Guard<'a>> {
+			match ShortCell::short_get(self.0) {
+				Ok(guard) => Unwrapped::Value(guard),
+				Err(marker) => Unwrapped::Missing(marker),
+			}
+		}
+
+		fn wrapper(self, value: &'a dyn Debug) -> CellWrapper<'a, T> {
+			CellWrapper(self.0, value)
+		}
+	}
+
+	// Cell around a value printed in place of the cell value, in the full output
+	pub struct OnceWrapper<'a, T: ?Sized>(&'a T, &'a dyn Debug);
+
+	impl<T: ShortOnce + ?Sized> Debug for OnceWrapper<'_, T> {
+		fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+			self.0.fmt_wrapper(self.1, fmt)
+		}
+	}
+
+	pub struct CellWrapper<'a, T: ?Sized>(&'a T, &'a dyn Debug);
+
+	impl<T: ShortCell + ?Sized> Debug for CellWrapper<'_, T> {
+		fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+			self.0.fmt_wrapper(self.1, fmt)
+		}
+	}
+
+	pub trait ViaPlain<'a> {
+		fn short_option(self) -> Unwrapped<'a, &'a Never>;
+
+		fn short_once(self) -> Unwrapped<'a, &'a Never>;
+
+		fn short_get(self) -> Unwrapped<'a, &'a Never>;
+
+		fn wrapper(self, value: &'a dyn Debug) -> &'a dyn Debug;
+	}
+
+	impl<'a, T: Debug> ViaPlain<'a> for Auto<'a, T> {
+		fn short_option(self) -> Unwrapped<'a, &'a Never> {
+			Unwrapped::Plain(self.0)
+		}
+
+		fn short_once(self) -> Unwrapped<'a, &'a Never> {
+			Unwrapped::Plain(self.0)
+		}
+
+		fn short_get(self) -> Unwrapped<'a, &'a Never> {
+			Unwrapped::Plain(self.0)
+		}
+
+		fn wrapper(self, value: &'a dyn Debug) -> &'a dyn Debug {
+			value
+		}
+	}
+
+	// Unwrapped value of plain types, that is never there
+	#[derive(Debug)]
+	pub enum Never {}
+
+	// Gives `Unwrapped::Value` the type of the field type argument: `(&&Cast(value)).cast()`
+	// returns the value itself, and the `Never` value of plain types as any type, as the code
+	// using it is still type checked
+	pub struct Cast<T>(pub T);
+
+	impl<T: Copy> Clone for Cast<T> {
+		fn clone(&self) -> Self {
+			*self
+		}
+	}
+
+	impl<T: Copy> Copy for Cast<T> {}
+
+	pub trait ViaNever<'a> {
+		fn cast<U: ?Sized>(self) -> &'a U;
+	}
+
+	impl<'a> ViaNever<'a> for &Cast<&'a Never> {
+		fn cast<U: ?Sized>(self) -> &'a U {
+			match *self.0 {}
+		}
+	}
+
+	pub trait ViaSame<'a> {
+		type Value: ?Sized;
+
+		fn cast(self) -> &'a Self::Value;
+	}
+
+	impl<'a, T: ?Sized> ViaSame<'a> for Cast<&'a T> {
+		type Value = T;
+
+		fn cast(self) -> &'a T {
+			self.0
+		}
+	}
+
+	// `Some(..)` around option values of `#[debug(keep_some_wrapper)]` fields
+	pub struct SomeWrapper<'a>(pub &'a dyn Debug);
+
+	impl Debug for SomeWrapper<'_> {
+		fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+			fmt.debug_tuple("Some").field(self.0).finish()
+		}
+	}
+
+	// Once cells, skipped like `None` while uninitialized.
+	// `fmt_wrapper` prints a value in place of the cell value, the way `Debug` of the cell does
+	pub trait ShortOnce {
+		type Value: ?Sized;
+
+		fn short_once(&self) -> Option<&Self::Value>;
+
+		fn fmt_wrapper(&self, value: &dyn Debug, fmt: &mut Formatter) -> fmt::Result;
+	}
+
+	impl<T> ShortOnce for OnceCell<T> {
+		type Value = T;
+
+		fn short_once(&self) -> Option<&T> {
+			self.get()
+		}
+
+		fn fmt_wrapper(&self, value: &dyn Debug, fmt: &mut Formatter) -> fmt::Result {
+			fmt.debug_tuple("OnceCell").field(value).finish()
+		}
+	}
+
+	#[cfg(feature = "std")]
+	impl<T> ShortOnce for std::sync::OnceLock<T> {
+		type Value = T;
+
+		fn short_once(&self) -> Option<&T> {
+			self.get()
+		}
+
+		fn fmt_wrapper(&self, value: &dyn Debug, fmt: &mut Formatter) -> fmt::Result {
+			fmt.debug_tuple("OnceLock").field(value).finish()
+		}
+	}
+
+	// Interior mutability cells, printed as their value without blocking,
+	// or as a marker when the value is borrowed mutably or locked
+	pub trait ShortCell {
+		type Value: ?Sized;
+		type Guard<'a>: Deref<Target = Self::Value>
+		where
+			Self: 'a;
+
+		fn short_get(&self) -> Result<Self::Guard<'_>, &'static str>;
+
+		fn fmt_wrapper(&self, value: &dyn Debug, fmt: &mut Formatter) -> fmt::Result;
+	}
+
+	// `Cell` value copied out of the cell
+	pub struct Copied<T>(T);
+
+	impl<T> Deref for Copied<T> {
+		type Target = T;
+
+		fn deref(&self) -> &T {
+			&self.0
+		}
+	}
+
+	impl<T: Copy> ShortCell for Cell<T> {
+		type Value = T;
+		type Guard<'a>
+			= Copied<T>
+		where
+			T: 'a;
+
+		fn short_get(&self) -> Result<Copied<T>, &'static str> {
+			Ok(Copied(self.get()))
+		}
+
+		fn fmt_wrapper(&self, value: &dyn Debug, fmt: &mut Formatter) -> fmt::Result {
+			fmt.debug_struct("Cell").field("value", value).finish()
+		}
+	}
+
+	impl<T: ?Sized> ShortCell for RefCell<T> {
+		type Value = T;
+		type Guard<'a>
+			= Ref<'a, T>
+		where
+			T: 'a;
+
+		fn short_get(&self) -> Result<Ref<'_, T>, &'static str> {
+			self.try_borrow().map_err(|_| "<borrowed>")
+		}
+
+		fn fmt_wrapper(&self, value: &dyn Debug, fmt: &mut Formatter) -> fmt::Result {
+			fmt.debug_struct("RefCell").field("value", value).finish()
+		}
+	}
+
+	// Poisoned locks still print their value, like std `Debug` does
+	#[cfg(feature = "std")]
+	impl<T: ?Sized> ShortCell for std::sync::Mutex<T> {
+		type Value = T;
+		type Guard<'a>
+			= std::sync::MutexGuard<'a, T>
+		where
+			T: 'a;
+
+		fn short_get(&self) -> Result<std::sync::MutexGuard<'_, T>, &'static str> {
+			match self.try_lock() {
+				Ok(guard) => Ok(guard),
+				Err(std::sync::TryLockError::Poisoned(err)) => Ok(err.into_inner()),
+				Err(std::sync::TryLockError::WouldBlock) => Err("<locked>"),
+			}
+		}
+
+		fn fmt_wrapper(&self, value: &dyn Debug, fmt: &mut Formatter) -> fmt::Result {
+			fmt.debug_struct("Mutex")
+				.field("data", value)
+				.field("poisoned", &self.is_poisoned())
+				.finish_non_exhaustive()
+		}
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: ?Sized> ShortCell for std::sync::RwLock<T> {
+		type Value = T;
+		type Guard<'a>
+			= std::sync::RwLockReadGuard<'a, T>
+		where
+			T: 'a;
+
+		fn short_get(&self) -> Result<std::sync::RwLockReadGuard<'_, T>, &'static str> {
+			match self.try_read() {
+				Ok(guard) => Ok(guard),
+				Err(std::sync::TryLockError::Poisoned(err)) => Ok(err.into_inner()),
+				Err(std::sync::TryLockError::WouldBlock) => Err("<locked>"),
+			}
+		}
+
+		fn fmt_wrapper(&self, value: &dyn Debug, fmt: &mut Formatter) -> fmt::Result {
+			fmt.debug_struct("RwLock")
+				.field("data", value)
+				.field("poisoned", &self.is_poisoned())
+				.finish_non_exhaustive()
+		}
+	}
+
+	// List or set of a `#[debug(elements)]` field,
+	// the iterator yields elements already shortened with `Auto`
+	pub struct ShortElements<I> {
+		iter: I,
+		set: bool,
+		drop_none: bool,
+	}
+
+	impl<I> ShortElements<I> {
+		pub fn new(iter: I, set: bool, drop_none: bool) -> Self {
+			Self { iter, set, drop_none }
+		}
+	}
+
+	impl<I, V> Debug for ShortElements<I>
+	where
+		I: Iterator<Item = Option<V>> + Clone,
+		V: Debug,
+	{
+		fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+			let elements = self.iter.clone();
+			match self.set {
+				true => {
+					let mut set = fmt.debug_set();
+					write_elements(elements, self.drop_none, |element| {
+						set.entry(element);
+					});
+					set.finish()
+				}
+				false => {
+					let mut list = fmt.debug_list();
+					write_elements(elements, self.drop_none, |element| {
+						list.entry(element);
+					});
+					list.finish()
+				}
+			}
+		}
+	}
+
+	// Writes elements unwrapped, and `None` ones as is or as a count at the end
+	fn write_elements<V: Debug>(
+		elements: impl Iterator<Item = Option<V>>,
+		drop_none: bool,
+		mut entry: impl FnMut(&dyn Debug),
+	) {
+		let mut dropped = 0;
+		for element in elements {
+			match element {
+				Some(value) => entry(&value),
+				None if drop_none => dropped += 1,
+				None => entry(&None::<()>),
+			}
+		}
+		if dropped > 0 {
+			entry(&format_args!("<{dropped} none>"));
+		}
+	}
+
+	// Map of a `#[debug(elements)]` field, the iterator yields values already shortened
+	pub struct ShortMap<I> {
+		iter: I,
+		drop_none: bool,
+	}
+
+	impl<I> ShortMap<I> {
+		pub fn new(iter: I, drop_none: bool) -> Self {
+			Self { iter, drop_none }
+		}
+	}
+
+	impl<I, K, V> Debug for ShortMap<I>
+	where
+		I: Iterator<Item = (K, Option<V>)> + Clone,
+		K: Debug,
+		V: Debug,
+	{
+		fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+			let mut map = fmt.debug_map();
+			let mut dropped = false;
+			for (key, value) in self.iter.clone() {
+				match value {
+					Some(value) => {
+						map.entry(&key, &value);
+					}
+					None if self.drop_none => dropped = true,
+					None => {
+						map.entry(&key, &None::<()>);
+					}
+				}
+			}
+			// Maps have no place for the count, dropped entries are marked with `..`
+			match dropped {
+				true => map.finish_non_exhaustive(),
+				false => map.finish(),
+			}
+		}
+	}
+}
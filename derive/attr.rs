@@ -0,0 +1,485 @@
+use crate::case::RenameRule;
+use crate::ty::{Shape, TypeKind};
+use syn::meta::ParseNestedMeta;
+use syn::parse::Parse;
+use syn::{Attribute, LitInt, LitStr, Path, Result, Token};
+
+// Options parsed from `#[debug(...)]` on the struct or enum itself
+#[derive(Default)]
+pub struct ContainerOpts {
+	// `#[debug(rename_all = "...")]`: rename struct fields or enum variants
+	pub rename_all: Option<RenameRule>,
+	// `#[debug(auto_redact)]` or `#[debug(auto_redact("extra", ...))]`: redact fields
+	// with sensitive names, holds the extra names
+	pub auto_redact: Option<Vec<String>>,
+	// `#[debug(skip_defaults)]`: don't print fields equal to their type's `Default::default()`
+	pub skip_defaults: bool,
+	// `#[debug(diff_default)]`: don't print fields equal to the same field of `Self::default()`
+	pub diff_default: bool,
+	// `#[debug(placeholder)]`: print skipped tuple fields as `_` instead of dropping them
+	pub placeholder: bool,
+	// `#[debug(auto)]`: shorten all fields by their type with `ShortValue`
+	pub auto: bool,
+	// `#[debug(elements)]`: shorten elements of all collection fields
+	pub elements: Option<Elements>,
+	// `#[debug(keep_none)]`: print `None` and uninitialized cells instead of skipping them
+	pub keep_none: bool,
+	// `#[debug(keep_empty)]`: print empty collections and strings instead of skipping them
+	pub keep_empty: bool,
+	// `#[debug(keep_some_wrapper)]`: print `Some(..)` around option values
+	pub keep_some_wrapper: bool,
+	// `#[debug(transparent)]`: print structs and all enum variants as their only field
+	pub transparent: bool,
+	// `#[debug(qualified)]`: print enum variants as `Enum::Variant`
+	pub qualified: bool,
+	// `#[debug(name = "...")]`: print the struct, or the enum in qualified names, as `name`
+	pub name: Option<LitStr>,
+	// `#[debug(anonymous)]`: print struct and variant fields without the name, as `{ a: 1 }`
+	pub anonymous: bool,
+	// `#[debug(show_generics)]` or `#[debug(show_generics(full))]`: print the generic
+	// arguments after the type name, as `Cache<u64, User>`
+	pub show_generics: Option<TypeNames>,
+	// `#[debug(mark_skipped)]` or `#[debug(count_skipped)]`: show that fields were skipped
+	pub mark_skipped: Option<SkippedMark>,
+	// `#[debug(full_in_alternate)]`: print everything like std `Debug` under `{:#?}`
+	pub full_in_alternate: bool,
+}
+
+// How skipped fields are shown at the end of the output
+#[derive(Clone, Copy)]
+pub enum SkippedMark {
+	// `Foo { a: 1, .. }`
+	Dots,
+	// `Foo { a: 1, ..3 hidden }`
+	Count,
+}
+
+// How type arguments are printed by `show_generics`
+#[derive(Clone, Copy)]
+pub enum TypeNames {
+	// Without module paths, `Vec<User>`
+	Short,
+	// As returned by `core::any::type_name`, `alloc::vec::Vec<app::User>`
+	Full,
+}
+
+// Built-in sensitive field names for `auto_redact`
+const SENSITIVE_NAMES: &[&str] = &[
+	"password",
+	"passwd",
+	"secret",
+	"token",
+	"api_key",
+	"authorization",
+	"private_key",
+	"credential",
+];
+
+impl ContainerOpts {
+	pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+		let mut opts = Self::default();
+		parse_debug_attrs(attrs, |meta| {
+			if meta.path.is_ident("rename_all") {
+				opts.rename_all = Some(parse_rename_rule(&meta)?);
+			}
+			else if meta.path.is_ident("auto_redact") {
+				let mut names = Vec::new();
+				if meta.input.peek(syn::token::Paren) {
+					let content;
+					syn::parenthesized!(content in meta.input);
+					let extra = content.parse_terminated(<LitStr as Parse>::parse, Token![,])?;
+					names.extend(extra.iter().map(LitStr::value));
+				}
+				opts.auto_redact = Some(names);
+			}
+			else if meta.path.is_ident("skip_defaults") {
+				opts.skip_defaults = true;
+			}
+			else if meta.path.is_ident("diff_default") {
+				opts.diff_default = true;
+			}
+			else if meta.path.is_ident("placeholder") {
+				opts.placeholder = true;
+			}
+			else if meta.path.is_ident("auto") {
+				opts.auto = true;
+			}
+			else if meta.path.is_ident("elements") {
+				opts.elements = Some(parse_elements(&meta)?);
+			}
+			else if meta.path.is_ident("keep_none") {
+				opts.keep_none = true;
+			}
+			else if meta.path.is_ident("keep_empty") {
+				opts.keep_empty = true;
+			}
+			else if meta.path.is_ident("keep_some_wrapper") {
+				opts.keep_some_wrapper = true;
+			}
+			else if meta.path.is_ident("transparent") {
+				opts.transparent = true;
+			}
+			else if meta.path.is_ident("qualified") {
+				opts.qualified = true;
+			}
+			else if meta.path.is_ident("name") {
+				opts.name = Some(meta.value()?.parse()?);
+			}
+			else if meta.path.is_ident("anonymous") {
+				opts.anonymous = true;
+			}
+			else if meta.path.is_ident("show_generics") {
+				opts.show_generics = Some(parse_type_names(&meta)?);
+			}
+			else if meta.path.is_ident("mark_skipped") {
+				opts.mark_skipped = Some(SkippedMark::Dots);
+			}
+			else if meta.path.is_ident("count_skipped") {
+				opts.mark_skipped = Some(SkippedMark::Count);
+			}
+			else if meta.path.is_ident("full_in_alternate") {
+				opts.full_in_alternate = true;
+			}
+			else {
+				return Err(meta.error("unknown `debug` container option"));
+			}
+			Ok(())
+		})?;
+		check_name(&opts.name, opts.anonymous)?;
+		Ok(opts)
+	}
+
+	// Whether `auto_redact` is enabled and the field name contains one of the sensitive names.
+	// Names are compared by whole words, so `db_password`, `apiKey` and `secret_key` match,
+	// but `tokenizer` and `secretary` don't
+	pub fn is_sensitive(&self, field: &str) -> bool {
+		let Some(extra) = &self.auto_redact
+		else {
+			return false;
+		};
+		let field = split_words(field);
+		SENSITIVE_NAMES
+			.iter()
+			.copied()
+			.chain(extra.iter().map(String::as_str))
+			.map(split_words)
+			.any(|name| !name.is_empty() && field.windows(name.len()).any(|words| words == name))
+	}
+}
+
+// Splits `db_password`, `apiKey` or `APIKey` into lowercase words
+fn split_words(name: &str) -> Vec<String> {
+	let chars: Vec<char> = name.chars().collect();
+	let mut words = Vec::new();
+	let mut word = String::new();
+	for (i, &c) in chars.iter().enumerate() {
+		let prev = i.checked_sub(1).map(|prev| chars[prev]);
+		let next = chars.get(i + 1);
+		let boundary = c.is_uppercase()
+			&& prev.is_some_and(|prev| {
+				!prev.is_uppercase() || next.is_some_and(|next| next.is_lowercase())
+			});
+		let separator = c == '_' || c == '-';
+		if (separator || boundary) && !word.is_empty() {
+			words.push(std::mem::take(&mut word));
+		}
+		if !separator {
+			word.extend(c.to_lowercase());
+		}
+	}
+	if !word.is_empty() {
+		words.push(word);
+	}
+	words
+}
+
+// Options parsed from `#[debug(...)]` on a single field
+#[derive(Default)]
+pub struct FieldOpts {
+	// `#[debug(skip)]`: never print the field
+	pub skip: bool,
+	// `#[debug(with = path::to::fn)]`: print the value with `fn(&T, &mut Formatter) -> fmt::Result`
+	pub with: Option<Path>,
+	// `#[debug(format = "{:#x}")]`: print the value through a `format_args!` template
+	pub format: Option<LitStr>,
+	// `#[debug(rename = "...")]`: print the field under another name
+	pub rename: Option<LitStr>,
+	// `#[debug(redact)]`, `#[debug(redact(last = N))]` or `#[debug(redact(hash))]`
+	pub redact: Option<Redact>,
+	// `#[debug(skip_if = path::to::fn)]`: don't print the field when `fn(&T) -> bool` is true
+	pub skip_if: Option<Path>,
+	// `#[debug(skip_default)]`: don't print the field when it equals `Default::default()`
+	pub skip_default: bool,
+	// `#[debug(option)]` or `#[debug(collection)]`: shorten the field as `Option` or as
+	// a `ShortEmpty` collection, for types that aren't recognized by their path.
+	// `#[debug(auto)]`: shorten the field by its type with `ShortValue`
+	pub kind: Option<TypeKind>,
+	// `#[debug(keep_some_none)]`: print `Some(None)` of nested options as `None`
+	pub keep_some_none: bool,
+	// `#[debug(elements)]` or `#[debug(elements(drop_none))]`: shorten collection elements
+	pub elements: Option<Elements>,
+	// `#[debug(keep)]`: never skip the field, it's printed even when `None` or empty
+	// and isn't affected by the container `skip_defaults`
+	pub keep: bool,
+	// `#[debug(keep_none)]`, `#[debug(keep_empty)]` and `#[debug(keep_some_wrapper)]`:
+	// same as the container options, for a single field
+	pub keep_none: bool,
+	pub keep_empty: bool,
+	pub keep_some_wrapper: bool,
+	// Not an attribute, set for the full output: cells are printed around their value
+	pub keep_cell_wrapper: bool,
+}
+
+// Element-level shortening of collections: `Some` is unwrapped, and with `drop_none`
+// `None` elements are dropped and counted at the end (`[1, 3, <2 none>]`)
+#[derive(Clone, Copy)]
+pub struct Elements {
+	pub drop_none: bool,
+}
+
+// How a redacted field is printed
+pub enum Redact {
+	// `***`
+	Full,
+	// `***` followed by the last N characters of the `Display` text,
+	// or of the `Debug` text for types without `Display`
+	Last(usize),
+	// `***#` followed by a short stable hash of the `Debug` text
+	Hash,
+}
+
+impl FieldOpts {
+	pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+		let mut opts = Self::default();
+		parse_debug_attrs(attrs, |meta| {
+			if meta.path.is_ident("skip") {
+				opts.skip = true;
+			}
+			else if meta.path.is_ident("skip_default") {
+				opts.skip_default = true;
+			}
+			else if meta.path.is_ident("option") {
+				opts.set_kind(&meta, TypeKind::Option)?;
+			}
+			else if meta.path.is_ident("collection") {
+				opts.set_kind(&meta, TypeKind::Collection(Shape::Opaque))?;
+			}
+			else if meta.path.is_ident("auto") {
+				opts.set_kind(&meta, TypeKind::Auto)?;
+			}
+			else if meta.path.is_ident("keep_some_none") {
+				opts.keep_some_none = true;
+			}
+			else if meta.path.is_ident("elements") {
+				opts.elements = Some(parse_elements(&meta)?);
+			}
+			else if meta.path.is_ident("keep") {
+				opts.keep = true;
+			}
+			else if meta.path.is_ident("keep_none") {
+				opts.keep_none = true;
+			}
+			else if meta.path.is_ident("keep_empty") {
+				opts.keep_empty = true;
+			}
+			else if meta.path.is_ident("keep_some_wrapper") {
+				opts.keep_some_wrapper = true;
+			}
+			else if meta.path.is_ident("skip_if") {
+				opts.skip_if = Some(meta.value()?.parse()?);
+			}
+			else if meta.path.is_ident("with") {
+				opts.check_no_formatter(&meta)?;
+				opts.with = Some(meta.value()?.parse()?);
+			}
+			else if meta.path.is_ident("format") {
+				opts.check_no_formatter(&meta)?;
+				opts.format = Some(meta.value()?.parse()?);
+			}
+			else if meta.path.is_ident("redact") {
+				opts.check_no_formatter(&meta)?;
+				opts.redact = Some(parse_redact(&meta)?);
+			}
+			else if meta.path.is_ident("rename") {
+				opts.rename = Some(meta.value()?.parse()?);
+			}
+			else {
+				return Err(meta.error("unknown `debug` field option"));
+			}
+			Ok(())
+		})?;
+		Ok(opts)
+	}
+
+	// `with`, `format` and `redact` all replace the way the value is printed
+	pub fn has_formatter(&self) -> bool {
+		self.with.is_some() || self.format.is_some() || self.redact.is_some()
+	}
+
+	fn set_kind(&mut self, meta: &ParseNestedMeta, kind: TypeKind) -> Result<()> {
+		if self.kind.is_some() {
+			return Err(meta.error("only one of `option`, `collection` and `auto` can be used"));
+		}
+		self.kind = Some(kind);
+		Ok(())
+	}
+
+	fn check_no_formatter(&self, meta: &ParseNestedMeta) -> Result<()> {
+		if self.has_formatter() {
+			return Err(meta.error("only one of `with`, `format` and `redact` can be used"));
+		}
+		Ok(())
+	}
+}
+
+// `name` and `anonymous` contradict each other
+fn check_name(name: &Option<LitStr>, anonymous: bool) -> Result<()> {
+	match name {
+		Some(name) if anonymous => {
+			Err(syn::Error::new(name.span(), "only one of `name` and `anonymous` can be used"))
+		}
+		_ => Ok(()),
+	}
+}
+
+// Upper bound for `redact(last = N)`, the suffix is buffered on the stack
+const MAX_REDACT_LAST: usize = 64;
+
+// Parses `redact`, `redact(last = N)` or `redact(hash)`
+fn parse_redact(meta: &ParseNestedMeta) -> Result<Redact> {
+	if !meta.input.peek(syn::token::Paren) {
+		return Ok(Redact::Full);
+	}
+	let mut redact = Redact::Full;
+	meta.parse_nested_meta(|meta| {
+		if !matches!(redact, Redact::Full) {
+			return Err(meta.error("only one `redact` mode can be used"));
+		}
+		if meta.path.is_ident("last") {
+			let last: LitInt = meta.value()?.parse()?;
+			match last.base10_parse()? {
+				0 => return Err(syn::Error::new(last.span(), "`last` must be positive")),
+				count if count > MAX_REDACT_LAST => {
+					let message = format!("`last` must be at most {MAX_REDACT_LAST}");
+					return Err(syn::Error::new(last.span(), message));
+				}
+				count => redact = Redact::Last(count),
+			}
+		}
+		else if meta.path.is_ident("hash") {
+			redact = Redact::Hash;
+		}
+		else {
+			return Err(meta.error("unknown `redact` mode, expected `last = N` or `hash`"));
+		}
+		Ok(())
+	})?;
+	Ok(redact)
+}
+
+// Parses `elements` or `elements(drop_none)`
+fn parse_elements(meta: &ParseNestedMeta) -> Result<Elements> {
+	let mut elements = Elements { drop_none: false };
+	if meta.input.peek(syn::token::Paren) {
+		meta.parse_nested_meta(|meta| {
+			if meta.path.is_ident("drop_none") {
+				elements.drop_none = true;
+				Ok(())
+			}
+			else {
+				Err(meta.error("unknown `elements` option, expected `drop_none`"))
+			}
+		})?;
+	}
+	Ok(elements)
+}
+
+// Parses `show_generics`, `show_generics(short)` or `show_generics(full)`
+fn parse_type_names(meta: &ParseNestedMeta) -> Result<TypeNames> {
+	let mut names = TypeNames::Short;
+	if meta.input.peek(syn::token::Paren) {
+		meta.parse_nested_meta(|meta| {
+			if meta.path.is_ident("short") {
+				names = TypeNames::Short;
+				Ok(())
+			}
+			else if meta.path.is_ident("full") {
+				names = TypeNames::Full;
+				Ok(())
+			}
+			else {
+				Err(meta.error("unknown `show_generics` option, expected `short` or `full`"))
+			}
+		})?;
+	}
+	Ok(names)
+}
+
+// Options parsed from `#[debug(...)]` on an enum variant
+#[derive(Default)]
+pub struct VariantOpts {
+	// `#[debug(skip)]`: print only the variant name, without its fields
+	pub skip: bool,
+	// `#[debug(rename_all = "...")]`: rename the variant fields
+	pub rename_all: Option<RenameRule>,
+	// `#[debug(placeholder)]`: print skipped tuple fields as `_` instead of dropping them
+	pub placeholder: bool,
+	// `#[debug(transparent)]`: print the variant as its only field
+	pub transparent: bool,
+	// `#[debug(name = "...")]`: print the variant as `name`
+	pub name: Option<LitStr>,
+	// `#[debug(anonymous)]`: print the variant fields without the name
+	pub anonymous: bool,
+}
+
+impl VariantOpts {
+	pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+		let mut opts = Self::default();
+		parse_debug_attrs(attrs, |meta| {
+			if meta.path.is_ident("skip") {
+				opts.skip = true;
+			}
+			else if meta.path.is_ident("rename_all") {
+				opts.rename_all = Some(parse_rename_rule(&meta)?);
+			}
+			else if meta.path.is_ident("placeholder") {
+				opts.placeholder = true;
+			}
+			else if meta.path.is_ident("transparent") {
+				opts.transparent = true;
+			}
+			else if meta.path.is_ident("name") {
+				opts.name = Some(meta.value()?.parse()?);
+			}
+			else if meta.path.is_ident("anonymous") {
+				opts.anonymous = true;
+			}
+			else {
+				return Err(meta.error("unknown `debug` variant option"));
+			}
+			Ok(())
+		})?;
+		check_name(&opts.name, opts.anonymous)?;
+		Ok(opts)
+	}
+}
+
+// Runs `parse` for every comma-separated item of every `#[debug(...)]` attribute
+fn parse_debug_attrs(
+	attrs: &[Attribute],
+	mut parse: impl FnMut(ParseNestedMeta) -> Result<()>,
+) -> Result<()> {
+	for attr in attrs.iter().filter(|attr| attr.path().is_ident("debug")) {
+		attr.parse_nested_meta(&mut parse)?;
+	}
+	Ok(())
+}
+
+// Parses `rename_all = "..."` value
+fn parse_rename_rule(meta: &ParseNestedMeta) -> Result<RenameRule> {
+	let name: LitStr = meta.value()?.parse()?;
+	RenameRule::from_name(&name.value()).ok_or_else(|| {
+		let names = RenameRule::names().collect::<Vec<_>>().join("\", \"");
+		syn::Error::new(name.span(), format!("unknown rename rule, expected one of \"{names}\""))
+	})
+}
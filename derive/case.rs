@@ -0,0 +1,95 @@
+// Case conversion rules for `#[debug(rename_all = "...")]`
+#[derive(Clone, Copy)]
+pub enum RenameRule {
+	Lower,
+	Upper,
+	Pascal,
+	Camel,
+	Snake,
+	ScreamingSnake,
+	Kebab,
+	ScreamingKebab,
+}
+
+impl RenameRule {
+	// Names accepted in `rename_all`, same as serde's
+	const RULES: &'static [(&'static str, RenameRule)] = &[
+		("lowercase", RenameRule::Lower),
+		("UPPERCASE", RenameRule::Upper),
+		("PascalCase", RenameRule::Pascal),
+		("camelCase", RenameRule::Camel),
+		("snake_case", RenameRule::Snake),
+		("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+		("kebab-case", RenameRule::Kebab),
+		("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebab),
+	];
+
+	pub fn from_name(name: &str) -> Option<Self> {
+		Self::RULES.iter().find(|(rule, _)| *rule == name).map(|(_, rule)| *rule)
+	}
+
+	pub fn names() -> impl Iterator<Item = &'static str> {
+		Self::RULES.iter().map(|(name, _)| *name)
+	}
+
+	// Converts a field name, which is expected to be snake_case
+	pub fn apply_to_field(self, field: &str) -> String {
+		match self {
+			Self::Lower | Self::Snake => field.to_owned(),
+			Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+			Self::Pascal => {
+				let mut pascal = String::new();
+				let mut capitalize = true;
+				for ch in field.chars() {
+					if ch == '_' {
+						capitalize = true;
+					}
+					else if capitalize {
+						pascal.push(ch.to_ascii_uppercase());
+						capitalize = false;
+					}
+					else {
+						pascal.push(ch);
+					}
+				}
+				pascal
+			}
+			Self::Camel => lowercase_first(&Self::Pascal.apply_to_field(field)),
+			Self::Kebab => field.replace('_', "-"),
+			Self::ScreamingKebab => Self::ScreamingSnake.apply_to_field(field).replace('_', "-"),
+		}
+	}
+
+	// Converts a variant name, which is expected to be Pascal
+	pub fn apply_to_variant(self, variant: &str) -> String {
+		match self {
+			Self::Pascal => variant.to_owned(),
+			Self::Lower => variant.to_ascii_lowercase(),
+			Self::Upper => variant.to_ascii_uppercase(),
+			Self::Camel => lowercase_first(variant),
+			Self::Snake => {
+				let mut snake = String::new();
+				for (i, ch) in variant.char_indices() {
+					if i > 0 && ch.is_uppercase() {
+						snake.push('_');
+					}
+					snake.push(ch.to_ascii_lowercase());
+				}
+				snake
+			}
+			Self::ScreamingSnake => Self::Snake.apply_to_variant(variant).to_ascii_uppercase(),
+			Self::Kebab => Self::Snake.apply_to_variant(variant).replace('_', "-"),
+			Self::ScreamingKebab => {
+				Self::ScreamingSnake.apply_to_variant(variant).replace('_', "-")
+			}
+		}
+	}
+}
+
+fn lowercase_first(name: &str) -> String {
+	let mut chars = name.chars();
+	match chars.next() {
+		Some(first) => first.to_lowercase().chain(chars).collect(),
+		None => String::new(),
+	}
+}
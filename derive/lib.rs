@@ -0,0 +1,960 @@
+mod attr;
+mod case;
+mod ty;
+
+use attr::{ContainerOpts, Elements, FieldOpts, Redact, SkippedMark, TypeNames, VariantOpts};
+use case::RenameRule;
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use std::ptr;
+use syn::ext::IdentExt;
+use syn::{parse_quote, Fields, LitStr};
+use synstructure::{decl_derive, AddBounds, BindingInfo, Structure, VariantInfo};
+use ty::{Shape, TypeKind};
+
+// custom `Debug`-like derive macro that does same thing as std::fmt::Debug
+// but skips Option::None and empty collection fields
+// and prints inner values of Option without Some(..) wrappers
+
+decl_derive!([ShortDebug, attributes(debug)] => custom_debug_derive);
+
+// Entry point of the derive macro implementation
+fn custom_debug_derive(mut structure: Structure) -> syn::Result<TokenStream> {
+	let container = ContainerOpts::from_attrs(&structure.ast().attrs)?;
+	if container.diff_default && !matches!(structure.ast().data, syn::Data::Struct(_)) {
+		return Err(syn::Error::new_spanned(
+			&structure.ast().ident,
+			"`diff_default` is only supported on structs",
+		));
+	}
+	if container.qualified && !matches!(structure.ast().data, syn::Data::Enum(_)) {
+		return Err(syn::Error::new_spanned(
+			&structure.ast().ident,
+			"`qualified` is only supported on enums",
+		));
+	}
+	if container.show_generics.is_some()
+		&& !container.qualified
+		&& matches!(structure.ast().data, syn::Data::Enum(_))
+	{
+		return Err(syn::Error::new_spanned(
+			&structure.ast().ident,
+			"`show_generics` on enums requires `qualified`, as the enum name isn't printed otherwise",
+		));
+	}
+
+	// Drop skipped fields from patterns and bounds, so they don't need to implement Debug.
+	// Malformed attributes are kept here and reported while generating the arm body
+	for variant in structure.variants_mut() {
+		let variant_skip = variant_opts(variant).is_ok_and(|opts| opts.skip);
+		variant.filter(|binding| {
+			!variant_skip
+				&& !FieldOpts::from_attrs(&binding.ast().attrs).is_ok_and(|opts| opts.skip)
+		});
+	}
+
+	// Add trait bounds to fields (e.g., require Debug on each field)
+	structure.add_bounds(AddBounds::Fields);
+
+	// Fields compared with their default value also need PartialEq, and Default
+	// when the default value is created from the field type.
+	// Option and cell fields only bound the whole field, while the value is printed alone.
+	// Generic `auto` fields and `elements` need ShortValue to be dispatched by their actual type,
+	// elements are printed as themselves too
+	let mut predicates: Vec<syn::WherePredicate> = Vec::new();
+	for binding in structure.variants().iter().flat_map(VariantInfo::bindings) {
+		if binding.referenced_ty_params().is_empty() {
+			continue;
+		}
+		let ty = &binding.ast().ty;
+		let opts = FieldOpts::from_attrs(&binding.ast().attrs).unwrap_or_default();
+		let kind = field_kind(&opts, &container, ty);
+		if let Some(TypeKind::Auto) = kind {
+			predicates.push(parse_quote! { #ty: ::short_debug::ShortValue });
+		}
+		// Values unwrapped from options and cells are printed as their own type
+		let (mut wrapper, mut wrapper_kind) = (ty, kind);
+		while let Some(TypeKind::Option | TypeKind::Once | TypeKind::Cell) = wrapper_kind {
+			let Some(value) = ty::first_type_arg(ty::peel_pointers(wrapper))
+			else {
+				break;
+			};
+			if !opts.has_formatter() {
+				predicates.push(parse_quote! { #value: ::core::fmt::Debug });
+			}
+			(wrapper, wrapper_kind) = (value, TypeKind::of(value));
+		}
+		let elements = opts.elements.or(container.elements).is_some() && !opts.has_formatter();
+		if let Some((key, element)) = ty::element_types(ty).filter(|_| elements) {
+			predicates.extend(key.map(|key| parse_quote! { #key: ::core::fmt::Debug }));
+			predicates.push(parse_quote! {
+				#element: ::short_debug::ShortValue + ::core::fmt::Debug
+			});
+		}
+		let skip_default = opts.skip_default || (container.skip_defaults && !opts.keep);
+		if container.diff_default {
+			predicates.push(parse_quote! { #ty: ::core::cmp::PartialEq });
+		}
+		else if skip_default {
+			predicates.push(parse_quote! {
+				#ty: ::core::cmp::PartialEq + ::core::default::Default
+			});
+		}
+	}
+	if container.diff_default {
+		predicates.push(parse_quote! { Self: ::core::default::Default });
+	}
+	for predicate in predicates {
+		structure.add_where_predicate(predicate);
+	}
+
+	// Generate match arms for each enum variant or struct constructor
+	let generic_args =
+		container.show_generics.map(|names| generate_generic_args(&structure, names));
+	let mut match_arms = TokenStream::new();
+	for variant in structure.variants() {
+		let pat = variant.pat();
+		let body = generate_match_arm_body(variant, &container, &generic_args)?;
+		match_arms.extend(quote! { #pat => { #body } });
+	}
+	let mut body = quote! { match *self { #match_arms } };
+
+	// `full_in_alternate` keeps the short output for `{:?}` only
+	if container.full_in_alternate {
+		let mut full_arms = TokenStream::new();
+		for variant in structure.variants() {
+			let pat = variant.pat();
+			let full_body = generate_full_arm_body(variant, &container)?;
+			full_arms.extend(quote! { #pat => { #full_body } });
+		}
+		body = quote! {
+			if fmt.alternate() {
+				match *self { #full_arms }
+			}
+			else {
+				#body
+			}
+		};
+	}
+
+	// Generate full `impl Debug for T` block, and `ShortValue` so the type
+	// can be used in generic `auto` fields of other types
+	Ok(structure.gen_impl(quote! {
+		gen impl ::core::fmt::Debug for @Self {
+			fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				#body
+			}
+		}
+
+		gen impl ::short_debug::ShortValue for @Self
+		where
+			Self: ::core::fmt::Debug,
+		{
+			type Inner = Self;
+
+			fn short_value(&self) -> ::core::option::Option<&Self> {
+				::core::option::Option::Some(self)
+			}
+		}
+	}))
+}
+
+// Generates `&[&dyn Display]` with names of the type arguments and values of the const
+// arguments for `show_generics`
+fn generate_generic_args(structure: &Structure, names: TypeNames) -> TokenStream {
+	let args = structure.ast().generics.params.iter().filter_map(|param| match param {
+		syn::GenericParam::Type(param) => {
+			let ident = &param.ident;
+			let type_name = quote! { ::core::any::type_name::<#ident>() };
+			Some(match names {
+				TypeNames::Short => quote! { &::short_debug::__private::ShortTypeName(#type_name) },
+				TypeNames::Full => quote! { &#type_name },
+			})
+		}
+		syn::GenericParam::Const(param) => {
+			let ident = &param.ident;
+			Some(quote! { &#ident })
+		}
+		syn::GenericParam::Lifetime(_) => None,
+	});
+	quote! { &[#(#args),*] }
+}
+
+// How the field is shortened: explicit hint, container `auto` mode or recognized type
+fn field_kind(opts: &FieldOpts, container: &ContainerOpts, ty: &syn::Type) -> Option<TypeKind> {
+	opts.kind.or(container.auto.then_some(TypeKind::Auto)).or_else(|| TypeKind::of(ty))
+}
+
+// Parses variant options; structs have no variant-level attributes of their own
+fn variant_opts(variant: &VariantInfo) -> syn::Result<VariantOpts> {
+	match variant.prefix {
+		Some(_) => VariantOpts::from_attrs(variant.ast().attrs),
+		None => Ok(VariantOpts::default()),
+	}
+}
+
+// Generates the body of a match arm for a single variant (struct or enum)
+fn generate_match_arm_body(
+	variant: &VariantInfo,
+	container: &ContainerOpts,
+	generic_args: &Option<TokenStream>,
+) -> syn::Result<TokenStream> {
+	let opts = variant_opts(variant)?;
+
+	// Name of the variant or struct. Container `rename_all` applies to enum variant names
+	// and struct field names, variant `rename_all` applies to the variant field names.
+	// Explicit `name` replaces the name as is. Enum names are only printed when qualified
+	let ident = variant.ast().ident.unraw().to_string();
+	let (type_name, variant_name, rename_fields) = match variant.prefix {
+		Some(enum_ident) => {
+			let name = match &opts.name {
+				Some(name) => name.value(),
+				None => {
+					container.rename_all.map_or(ident.clone(), |rule| rule.apply_to_variant(&ident))
+				}
+			};
+			let enum_name = container.qualified.then(|| {
+				container.name.as_ref().map_or(enum_ident.unraw().to_string(), LitStr::value)
+			});
+			(enum_name, Some(name), opts.rename_all)
+		}
+		None => {
+			let name = container.name.as_ref().map_or(ident, LitStr::value);
+			(Some(name), None, container.rename_all)
+		}
+	};
+
+	// With `show_generics` the type name is followed by the generic arguments,
+	// the name is formatted at runtime then
+	let (name_stmt, name) = match (generic_args, type_name) {
+		(Some(args), Some(type_name)) => {
+			let suffix = variant_name.map_or(String::new(), |name| format!("::{name}"));
+			let name_stmt = quote! {
+				let name = ::short_debug::__private::generic_name(#type_name, #args, #suffix);
+			};
+			(name_stmt, quote! { &name })
+		}
+		(_, type_name) => {
+			let name = [type_name, variant_name].into_iter().flatten().collect::<Vec<_>>();
+			let name = name.join("::");
+			(TokenStream::new(), quote! { #name })
+		}
+	};
+
+	// Skipped variants print only their name
+	if opts.skip {
+		return Ok(quote! {
+			#name_stmt
+			fmt.write_str(#name)
+		});
+	}
+
+	// `diff_default` compares fields with a single default value of the struct
+	let default_value = container.diff_default.then(|| {
+		quote! { let default_value = <Self as ::core::default::Default>::default(); }
+	});
+
+	// Transparent variants print their only field in place of themselves,
+	// and only their name when the field is skipped at runtime
+	if container.transparent || opts.transparent {
+		let [binding] = variant.bindings()
+		else {
+			return Err(syn::Error::new_spanned(
+				variant.ast().ident,
+				"`transparent` requires exactly one field that isn't skipped",
+			));
+		};
+		let index = variant.ast().fields.iter().position(|field| ptr::eq(binding.ast(), field));
+		let ctx = FieldsCtx {
+			container,
+			rename_all: rename_fields,
+			on_skip: quote! { return fmt.write_str(#name); },
+			transparent: true,
+		};
+		let call = generate_debug_builder_call(binding, index.unwrap_or_default(), &ctx)?;
+		return Ok(quote! {
+			#name_stmt
+			#default_value
+			#call
+		});
+	}
+
+	// Choose debug struct/tuple builder based on field style.
+	// Anonymous fields are printed without the name, unit structs and variants keep it
+	// as nothing would be printed otherwise.
+	// Skipped tuple fields may be kept as `_` placeholders to preserve positions
+	// Std `debug_struct` can't print anonymous structs, and neither std builder can end
+	// with a skipped field count
+	let anonymous = container.anonymous || opts.anonymous;
+	let own_builder = anonymous || matches!(container.mark_skipped, Some(SkippedMark::Count));
+	let builder_name = match anonymous {
+		true => quote! { "" },
+		false => name.clone(),
+	};
+	let debug_builder = match variant.ast().fields {
+		Fields::Named(_) if own_builder => {
+			quote! { ::short_debug::__private::DebugStruct::new(fmt, #builder_name) }
+		}
+		Fields::Unnamed(_) if own_builder => {
+			quote! { ::short_debug::__private::DebugTuple::new(fmt, #builder_name) }
+		}
+		Fields::Named(_) | Fields::Unit => quote! { fmt.debug_struct(#name) },
+		Fields::Unnamed(_) => quote! { fmt.debug_tuple(#name) },
+	};
+
+	// Skipped tuple fields may be kept as `_` placeholders, otherwise they are counted
+	// for `mark_skipped` and `count_skipped`
+	let placeholder = match variant.ast().fields {
+		Fields::Unnamed(_) => container.placeholder || opts.placeholder,
+		Fields::Named(_) | Fields::Unit => false,
+	};
+	let mark_skipped = container
+		.mark_skipped
+		.filter(|_| !placeholder && !matches!(variant.ast().fields, Fields::Unit));
+	let (counter, on_skip) = match (placeholder, mark_skipped) {
+		(true, _) => (None, quote! { debug_builder.field(&::core::format_args!("_")); }),
+		(false, Some(_)) => (
+			Some(quote! { let skipped = ::core::cell::Cell::new(0usize); }),
+			quote! { skipped.set(skipped.get() + 1); },
+		),
+		(false, None) => (None, TokenStream::new()),
+	};
+	let finish = match (&variant.ast().fields, mark_skipped) {
+		(_, None) => quote! { debug_builder.finish() },
+		(_, Some(SkippedMark::Dots)) => quote! {
+			match skipped.get() {
+				0 => debug_builder.finish(),
+				_ => debug_builder.finish_non_exhaustive(),
+			}
+		},
+		(_, Some(SkippedMark::Count)) => quote! { debug_builder.finish_hidden(skipped.get()) },
+	};
+	let ctx = FieldsCtx { container, rename_all: rename_fields, on_skip, transparent: false };
+
+	// Generate `.field(...)` or conditional field calls
+	let mut debug_builder_calls = Vec::new();
+	for (index, field) in variant.ast().fields.iter().enumerate() {
+		match variant.bindings().iter().find(|binding| ptr::eq(binding.ast(), field)) {
+			Some(binding) => {
+				debug_builder_calls.push(generate_debug_builder_call(binding, index, &ctx)?)
+			}
+			// Bindings of `#[debug(skip)]` fields are filtered out
+			None => debug_builder_calls.push(ctx.on_skip.clone()),
+		}
+	}
+
+	// Generate code like:
+	// let mut debug_builder = fmt.debug_struct("VariantName");
+	// debug_builder.field("field", value);
+	// debug_builder.finish()
+	Ok(quote! {
+		#name_stmt
+		#default_value
+		#counter
+		let mut debug_builder = #debug_builder;
+		#(#debug_builder_calls)*
+		#finish
+	})
+}
+
+// Generates the body of a match arm printed like std `Debug` for `full_in_alternate`:
+// nothing is shortened or renamed, but formatters and redaction still apply to the values
+// inside options and cells, and `#[debug(skip)]` fields are marked with `..` as they may not implement Debug
+fn generate_full_arm_body(
+	variant: &VariantInfo,
+	container: &ContainerOpts,
+) -> syn::Result<TokenStream> {
+	let name = variant.ast().ident.unraw().to_string();
+	let mut calls = Vec::new();
+	for binding in variant.bindings() {
+		let mut opts = FieldOpts::from_attrs(&binding.ast().attrs)?;
+		let ident = binding.ast().ident.as_ref().map(|ident| ident.unraw().to_string());
+		if let Some(ident) = &ident {
+			if !opts.has_formatter() && container.is_sensitive(ident) {
+				opts.redact = Some(Redact::Full);
+			}
+		}
+		if opts.has_formatter() {
+			// Formatters get the value inside options and cells like in the short output,
+			// so these are still unwrapped, but nothing is skipped and the wrappers are kept.
+			// Types are recognized by path, the container `auto` only applies to short output
+			opts.keep_none = true;
+			opts.keep_empty = true;
+			opts.keep_some_wrapper = true;
+			opts.keep_cell_wrapper = true;
+			let ctx = FieldsCtx {
+				container,
+				rename_all: None,
+				on_skip: TokenStream::new(),
+				transparent: false,
+			};
+			let ty = &binding.ast().ty;
+			let kind = match opts.kind {
+				Some(TypeKind::Auto) | None => TypeKind::of(ty),
+				kind => kind,
+			};
+			calls.push(generate_field_call(
+				&opts,
+				kind,
+				ident.as_deref(),
+				ty,
+				&quote! { #binding },
+				&ctx,
+			));
+			continue;
+		}
+		calls.push(match ident {
+			Some(ident) => quote! { debug_builder.field(#ident, #binding); },
+			None => quote! { debug_builder.field(#binding); },
+		});
+	}
+
+	let debug_builder = match variant.ast().fields {
+		Fields::Named(_) | Fields::Unit => quote! { fmt.debug_struct(#name) },
+		Fields::Unnamed(_) => quote! { fmt.debug_tuple(#name) },
+	};
+	let finish = match variant.bindings().len() < variant.ast().fields.len() {
+		true => quote! { debug_builder.finish_non_exhaustive() },
+		false => quote! { debug_builder.finish() },
+	};
+	Ok(quote! {
+		let mut debug_builder = #debug_builder;
+		#(#calls)*
+		#finish
+	})
+}
+
+// Settings shared by all fields of a variant
+struct FieldsCtx<'a> {
+	container: &'a ContainerOpts,
+	// Rule for field names, from the container for structs or from the variant for enums
+	rename_all: Option<RenameRule>,
+	// Code generated in place of a field skipped at runtime
+	on_skip: TokenStream,
+	// The field is printed directly with its `Debug`, and is never shortened away
+	transparent: bool,
+}
+
+// Generates code for a single `.field(...)` call in the builder
+fn generate_debug_builder_call(
+	binding: &BindingInfo,
+	index: usize,
+	ctx: &FieldsCtx,
+) -> syn::Result<TokenStream> {
+	let container = ctx.container;
+	// Skipped fields are already filtered out at this point
+	let mut opts = FieldOpts::from_attrs(&binding.ast().attrs)?;
+
+	let format = quote! { #binding };
+	opts.elements = opts.elements.or(container.elements);
+	opts.keep |= ctx.transparent;
+	opts.keep_none |= opts.keep || container.keep_none;
+	opts.keep_empty |= opts.keep || container.keep_empty;
+	opts.keep_some_wrapper |= container.keep_some_wrapper;
+
+	// Value the field is compared with by `skip_default`, `skip_defaults` and `diff_default`
+	let default = if container.diff_default {
+		let member = match &binding.ast().ident {
+			Some(ident) => syn::Member::Named(ident.clone()),
+			None => syn::Member::Unnamed(index.into()),
+		};
+		Some(quote! { &default_value.#member })
+	}
+	else if opts.skip_default || (container.skip_defaults && !opts.keep) {
+		let ty = &binding.ast().ty;
+		Some(quote! { &<#ty as ::core::default::Default>::default() })
+	}
+	else {
+		None
+	};
+
+	// Field name, tuple fields have none
+	let name = match binding.ast().ident.as_ref().map(|ident| ident.unraw().to_string()) {
+		Some(ident) => {
+			// Sensitive names are redacted unless the field chose how to print itself
+			if !opts.has_formatter() && container.is_sensitive(&ident) {
+				opts.redact = Some(Redact::Full);
+			}
+
+			Some(match (&opts.rename, ctx.rename_all) {
+				(Some(rename), _) => rename.value(),
+				(None, Some(rule)) => rule.apply_to_field(&ident),
+				(None, None) => ident,
+			})
+		}
+		None => {
+			if let Some(rename) = &opts.rename {
+				return Err(syn::Error::new(
+					rename.span(),
+					"`rename` is not supported on unnamed fields",
+				));
+			}
+			None
+		}
+	};
+
+	let ty = &binding.ast().ty;
+	let kind = field_kind(&opts, container, ty);
+	let call = generate_field_call(&opts, kind, name.as_deref(), ty, &format, ctx);
+	Ok(generate_skip_guards(&opts, &format, default, call, ctx))
+}
+
+// Generates `.field(...)` call for a field, shortening Option and collection values
+fn generate_field_call(
+	opts: &FieldOpts,
+	kind: Option<TypeKind>,
+	name: Option<&str>,
+	ty: &syn::Type,
+	format: &TokenStream,
+	ctx: &FieldsCtx,
+) -> TokenStream {
+	let field = |value: TokenStream| match (ctx.transparent, name) {
+		(true, _) => quote! { return ::core::fmt::Debug::fmt(#value, fmt); },
+		(false, Some(name)) => quote! { debug_builder.field(#name, #value); },
+		(false, None) => quote! { debug_builder.field(#value); },
+	};
+	generate_shortened_call(opts, kind, ty, format, &field, &generate_else(ctx))
+}
+
+// Generates the field call for a `value` reference of type `ty`, skipping it when it's
+// `None` or empty. Nested options and options of collections are unwrapped recursively
+fn generate_shortened_call(
+	opts: &FieldOpts,
+	kind: Option<TypeKind>,
+	ty: &syn::Type,
+	value: &TokenStream,
+	field: &dyn Fn(TokenStream) -> TokenStream,
+	on_skip: &Option<TokenStream>,
+) -> TokenStream {
+	// Options and cells behind transparent pointers are matched on the pointee, `&**` derefs
+	// both the reference and the pointer. Collections are checked through the pointer
+	// with `ShortEmpty`, as the pointee may be unsized
+	if let (Some(TypeKind::Option | TypeKind::Once | TypeKind::Cell), Some(pointee)) =
+		(kind, ty::pointee(ty))
+	{
+		return generate_shortened_call(opts, kind, pointee, &quote! { &**#value }, field, on_skip);
+	}
+
+	// Handle special-case field types: Option<T>, cells and collections
+	match kind {
+		Some(TypeKind::Option) => {
+			// Only print Some(...) values, `Some(empty)` and `Some(None)` are skipped too.
+			// With `keep_some_wrapper` the values are printed inside `Some(..)`
+			let wrapped_field = |value: TokenStream| {
+				field(quote! { &::short_debug::__private::SomeWrapper(#value) })
+			};
+			let inner_field: &dyn Fn(TokenStream) -> TokenStream = match opts.keep_some_wrapper {
+				true => &wrapped_field,
+				false => field,
+			};
+			// With `keep_some_none` the skipped inner value is printed instead,
+			// `v` is still bound to it in the `else` branch
+			let inner_on_skip = match opts.keep_some_none {
+				true => {
+					let call = inner_field(quote! { v });
+					Some(quote! { else { #call } })
+				}
+				false => on_skip.clone(),
+			};
+			let call = generate_wrapped_call(opts, ty, inner_field, &inner_on_skip);
+			let on_none = generate_keep(opts.keep_none, "None", field).or_else(|| on_skip.clone());
+			// Types that are only named `Option` are printed as they are,
+			// unless the field is marked with `#[debug(option)]`
+			let unwrapped = match opts.kind {
+				Some(TypeKind::Option) => quote! { ::short_debug::__private::short_option(#value) },
+				_ => quote! {{
+					use ::short_debug::__private::{ViaOption as _, ViaPlain as _};
+					(&&::short_debug::__private::Auto(#value)).short_option()
+				}},
+			};
+			generate_unwrapped_call(opts, &unwrapped, call, on_none, field)
+		}
+		Some(TypeKind::Once) => {
+			// Uninitialized cells are skipped, and are never forced
+			let wrapper = generate_cell_wrapper(opts, ty, value, quote! { ViaShortOnce });
+			let cell = wrapper.target();
+			let wrapped_field = |value: TokenStream| field(wrapper.wrap(value));
+			let call = wrapper.import(generate_wrapped_call(opts, ty, &wrapped_field, on_skip));
+			// The kept wrapper prints like `Debug` of the cell, `OnceCell(<uninit>)`
+			let on_uninit = match &wrapper.cell {
+				Some(cell) => {
+					let call = field(quote! { #cell });
+					Some(quote! { else { #call } })
+				}
+				None => generate_keep(opts.keep_none, "<uninit>", field),
+			};
+			let on_uninit = on_uninit.or_else(|| on_skip.clone());
+			let unwrapped = quote! {{
+				use ::short_debug::__private::{ViaPlain as _, ViaShortOnce as _};
+				(&&::short_debug::__private::Auto(#cell)).short_once()
+			}};
+			wrapper.bind(generate_unwrapped_call(opts, &unwrapped, call, on_uninit, field))
+		}
+		Some(TypeKind::Cell) => {
+			// Borrowed or locked values are never waited for, a marker is printed instead
+			let wrapper = generate_cell_wrapper(opts, ty, value, quote! { ViaShortCell });
+			let cell = wrapper.target();
+			let wrapped_field = |value: TokenStream| field(wrapper.wrap(value));
+			let call = generate_wrapped_call(opts, ty, &wrapped_field, on_skip);
+			let call = wrapper.import(quote! {
+				let v = &*v;
+				#call
+			});
+			// The kept wrapper prints like `Debug` of the cell, `RefCell { value: <borrowed> }`
+			let marker = match &wrapper.cell {
+				Some(cell) => field(quote! { #cell }),
+				None => field(quote! { &::core::format_args!("{}", marker) }),
+			};
+			let on_busy = quote! {
+				else if let ::short_debug::__private::Unwrapped::Missing(marker) = unwrapped {
+					#marker
+				}
+			};
+			let unwrapped = quote! {{
+				use ::short_debug::__private::{ViaPlain as _, ViaShortCell as _};
+				(&&::short_debug::__private::Auto(#cell)).short_get()
+			}};
+			wrapper.bind(generate_unwrapped_call(opts, &unwrapped, call, Some(on_busy), field))
+		}
+		Some(TypeKind::Collection(shape)) => {
+			// Only print non-empty collections and strings
+			let elements = opts.elements.filter(|_| !opts.has_formatter());
+			let value_arg =
+				match elements.and_then(|elements| generate_elements(elements, shape, value)) {
+					Some(elements) => elements,
+					None => generate_field_value(opts, value),
+				};
+			let call = field(value_arg);
+			if opts.keep_empty {
+				return call;
+			}
+			// Types marked with `#[debug(collection)]` must implement `ShortEmpty`,
+			// recognized ones are printed as is without it
+			let is_empty = match opts.kind {
+				Some(TypeKind::Collection(_)) => {
+					quote! { ::short_debug::ShortEmpty::is_short_empty(#value) }
+				}
+				_ => generate_short_empty(value),
+			};
+			quote! {
+				if !#is_empty { #call } #on_skip
+			}
+		}
+		Some(TypeKind::Auto) => {
+			// Let the field type decide what to print, if anything.
+			// The value may be unsized, like `str`, so it's passed by reference
+			let call = field(generate_field_value(opts, &quote! { &v }));
+			let short_value = generate_short_value(value);
+			// `ShortValue` doesn't tell `None` from empty, kept values are printed as is.
+			// The formatter expects the inner type, so it isn't used for them
+			let on_skip = match opts.keep_none || opts.keep_empty {
+				true => {
+					let call = field(value.clone());
+					Some(quote! { else { #call } })
+				}
+				false => on_skip.clone(),
+			};
+			quote! {
+				if let ::core::option::Option::Some(v) = #short_value { #call } #on_skip
+			}
+		}
+		// Default: always print the value
+		None => field(generate_field_value(opts, value)),
+	}
+}
+
+// Generates the field call for an `Unwrapped` option or cell value: `call` for the value,
+// `on_missing` for `None` and uninitialized cells, or the value itself for plain types
+fn generate_unwrapped_call(
+	opts: &FieldOpts,
+	unwrapped: &TokenStream,
+	call: TokenStream,
+	on_missing: Option<TokenStream>,
+	field: &dyn Fn(TokenStream) -> TokenStream,
+) -> TokenStream {
+	let plain = field(generate_plain_value(opts, &quote! { v }));
+	quote! {{
+		let unwrapped = #unwrapped;
+		if let ::short_debug::__private::Unwrapped::Value(v) = unwrapped { #call }
+		else if let ::short_debug::__private::Unwrapped::Plain(v) = unwrapped { #plain }
+		#on_missing
+	}}
+}
+
+// Generates the `else` branch printing `text` in place of a kept `None` value
+fn generate_keep(
+	keep: bool,
+	text: &str,
+	field: &dyn Fn(TokenStream) -> TokenStream,
+) -> Option<TokenStream> {
+	let call = field(quote! { &::core::format_args!(#text) });
+	keep.then(|| quote! { else { #call } })
+}
+
+// Cell of the full output, printed around its value with `Debug` of the cell
+struct CellWrapper {
+	value: TokenStream,
+	// Binding of the cell reference, as `v` is shadowed by the unwrapped values.
+	// Only set with `keep_cell_wrapper`
+	cell: Option<Ident>,
+	via: TokenStream,
+}
+
+impl CellWrapper {
+	// Prints `value` inside the cell wrapper
+	fn wrap(&self, value: TokenStream) -> TokenStream {
+		match &self.cell {
+			Some(cell) => quote! { &(&&::short_debug::__private::Auto(#cell)).wrapper(#value) },
+			None => value,
+		}
+	}
+
+	// Brings the `wrapper` method into scope for the value call
+	fn import(&self, call: TokenStream) -> TokenStream {
+		let via = &self.via;
+		match &self.cell {
+			Some(_) => quote! {
+				use ::short_debug::__private::{ViaPlain as _, #via as _};
+				#call
+			},
+			None => call,
+		}
+	}
+
+	// The cell reference, bound or not
+	fn target(&self) -> TokenStream {
+		match &self.cell {
+			Some(cell) => quote! { #cell },
+			None => self.value.clone(),
+		}
+	}
+
+	// Binds the cell reference around the whole field call
+	fn bind(&self, call: TokenStream) -> TokenStream {
+		let value = &self.value;
+		match &self.cell {
+			Some(cell) => quote! {{
+				let #cell = #value;
+				#call
+			}},
+			None => call,
+		}
+	}
+}
+
+fn generate_cell_wrapper(
+	opts: &FieldOpts,
+	ty: &syn::Type,
+	value: &TokenStream,
+	via: TokenStream,
+) -> CellWrapper {
+	// Cells nested in each other are told apart by the depth of their type
+	let depth = std::iter::successors(Some(ty), |ty| ty::first_type_arg(ty)).count();
+	let cell = opts.keep_cell_wrapper.then(|| format_ident!("cell_{}", depth));
+	CellWrapper { value: value.clone(), cell, via }
+}
+
+// Generates the field call for the value `v` unwrapped from an option or a cell of type `ty`,
+// shortened by its own type
+fn generate_wrapped_call(
+	opts: &FieldOpts,
+	ty: &syn::Type,
+	field: &dyn Fn(TokenStream) -> TokenStream,
+	on_skip: &Option<TokenStream>,
+) -> TokenStream {
+	match ty::first_type_arg(ty) {
+		Some(inner) => {
+			let call = generate_shortened_call(
+				opts,
+				TypeKind::of(inner),
+				inner,
+				&quote! { v },
+				field,
+				on_skip,
+			);
+			// The value of plain types is never there, but the call is still type checked
+			quote! {
+				let v: &#inner = {
+					use ::short_debug::__private::{ViaNever as _, ViaSame as _};
+					(&&::short_debug::__private::Cast(v)).cast()
+				};
+				#call
+			}
+		}
+		None => field(generate_field_value(opts, &quote! { v })),
+	}
+}
+
+// Generates `Option<&T>` expression that shortens the `value` reference by its type
+fn generate_short_value(value: &TokenStream) -> TokenStream {
+	quote! {{
+		use ::short_debug::__private::{ViaDebug as _, ViaOptionDebug as _, ViaShortValue as _};
+		(&&&::short_debug::__private::Auto(#value)).short_value()
+	}}
+}
+
+fn generate_short_element(value: &TokenStream) -> TokenStream {
+	quote! {{
+		use ::short_debug::__private::{
+			ViaDebugElement as _, ViaOptionElement as _, ViaShortElement as _,
+		};
+		(&&&::short_debug::__private::Auto(#value)).short_element()
+	}}
+}
+
+fn generate_short_empty(value: &TokenStream) -> TokenStream {
+	quote! {{
+		use ::short_debug::__private::{ViaNonEmpty as _, ViaShortEmpty as _};
+		(&&::short_debug::__private::Auto(#value)).short_empty()
+	}}
+}
+
+// Generates an adapter printing collection elements with options unwrapped,
+// generic elements get a `ShortValue` bound like `auto` fields
+fn generate_elements(elements: Elements, shape: Shape, value: &TokenStream) -> Option<TokenStream> {
+	let drop_none = elements.drop_none;
+	match shape {
+		Shape::List | Shape::Set => {
+			let set = matches!(shape, Shape::Set);
+			let element = generate_short_element(&quote! { element });
+			Some(quote! {
+				&::short_debug::__private::ShortElements::new(
+					(#value).iter().map(|element| #element),
+					#set,
+					#drop_none,
+				)
+			})
+		}
+		Shape::Map => {
+			let element = generate_short_element(&quote! { value });
+			Some(quote! {
+				&::short_debug::__private::ShortMap::new(
+					(#value).iter().map(|(key, value)| (key, #element)),
+					#drop_none,
+				)
+			})
+		}
+		Shape::Opaque => None,
+	}
+}
+
+// Guards the field call with the default value comparison and the user `skip_if` predicate,
+// both get the whole field
+fn generate_skip_guards(
+	opts: &FieldOpts,
+	format: &TokenStream,
+	default: Option<TokenStream>,
+	mut call: TokenStream,
+	ctx: &FieldsCtx,
+) -> TokenStream {
+	let on_skip = generate_else(ctx);
+	if let Some(default) = default {
+		call = quote! { if #format != #default { #call } #on_skip };
+	}
+	if let Some(skip_if) = &opts.skip_if {
+		call = quote! { if !#skip_if(#format) { #call } #on_skip };
+	}
+	call
+}
+
+// Generates `else` branch for a runtime skip condition, if skipped fields leave a trace
+fn generate_else(ctx: &FieldsCtx) -> Option<TokenStream> {
+	let on_skip = &ctx.on_skip;
+	(!on_skip.is_empty()).then(|| quote! { else { #on_skip } })
+}
+
+// Generates the `&dyn Debug` expression for a value printed as it is, as formatters expect
+// the unwrapped value. Redacted fields stay hidden
+fn generate_plain_value(opts: &FieldOpts, value: &TokenStream) -> TokenStream {
+	match opts.redact {
+		Some(_) => quote! { &::core::format_args!("***") },
+		None => value.clone(),
+	}
+}
+
+// Generates the `&dyn Debug` expression passed to `.field(...)` for a (possibly unwrapped) value
+fn generate_field_value(opts: &FieldOpts, value: &TokenStream) -> TokenStream {
+	// Custom formatter function or format template
+	let write = if let Some(with) = &opts.with {
+		quote! { #with(#value, fmt) }
+	}
+	else if let Some(format) = &opts.format {
+		quote! { ::core::write!(fmt, #format, #value) }
+	}
+	else if let Some(redact) = &opts.redact {
+		generate_redacted_write(redact, value)
+	}
+	else {
+		return value.clone();
+	};
+
+	// Wrap the value into an adapter whose Debug impl calls the formatter
+	quote! {
+		&{
+			struct DebugFn<F>(F)
+			where
+				F: ::core::ops::Fn(&mut ::core::fmt::Formatter) -> ::core::fmt::Result;
+			impl<F> ::core::fmt::Debug for DebugFn<F>
+			where
+				F: ::core::ops::Fn(&mut ::core::fmt::Formatter) -> ::core::fmt::Result,
+			{
+				fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+					(self.0)(fmt)
+				}
+			}
+			DebugFn(|fmt: &mut ::core::fmt::Formatter| #write)
+		}
+	}
+}
+
+// Generates code that writes a redacted value into `fmt`
+fn generate_redacted_write(redact: &Redact, value: &TokenStream) -> TokenStream {
+	match *redact {
+		Redact::Full => quote! { fmt.write_str("***") },
+		Redact::Last(count) => quote! {{
+			// Keeps only the last #count characters written into it
+			struct Suffix {
+				chars: [char; #count],
+				written: usize,
+			}
+			impl ::core::fmt::Write for Suffix {
+				fn write_str(&mut self, s: &str) -> ::core::fmt::Result {
+					for ch in s.chars() {
+						self.chars[self.written % #count] = ch;
+						self.written += 1;
+					}
+					::core::result::Result::Ok(())
+				}
+			}
+			let mut suffix = Suffix { chars: ['\0'; #count], written: 0 };
+			let text = {
+				use ::short_debug::__private::{ViaDebugText as _, ViaDisplay as _};
+				(&&::short_debug::__private::Auto(#value)).redact_text()
+			};
+			::core::fmt::Write::write_fmt(&mut suffix, ::core::format_args!("{}", text))?;
+			fmt.write_str("***")?;
+			// Values not longer than the suffix are hidden completely
+			if suffix.written > #count {
+				for i in suffix.written - #count..suffix.written {
+					::core::fmt::Write::write_char(fmt, suffix.chars[i % #count])?;
+				}
+			}
+			::core::result::Result::Ok(())
+		}},
+		Redact::Hash => quote! {{
+			// 64-bit FNV-1a, stable across runs and platforms
+			struct Fnv(u64);
+			impl ::core::fmt::Write for Fnv {
+				fn write_str(&mut self, s: &str) -> ::core::fmt::Result {
+					for byte in s.bytes() {
+						self.0 = (self.0 ^ byte as u64).wrapping_mul(0x100000001b3);
+					}
+					::core::result::Result::Ok(())
+				}
+			}
+			let mut hash = Fnv(0xcbf29ce484222325);
+			::core::fmt::Write::write_fmt(&mut hash, ::core::format_args!("{:?}", #value))?;
+			::core::write!(fmt, "***#{:08x}", (hash.0 ^ (hash.0 >> 32)) as u32)
+		}},
+	}
+}
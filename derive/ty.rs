@@ -0,0 +1,261 @@
+use syn::{GenericArgument, Path, PathArguments, Type, TypePath};
+
+// Field types that get shortened
+#[derive(Clone, Copy)]
+pub enum TypeKind {
+	// Skipped when `None`, printed without `Some(..)` otherwise
+	Option,
+	// Skipped when `ShortEmpty::is_short_empty()`
+	Collection(Shape),
+	// Dispatched on the type with `ShortValue`, never recognized by path
+	Auto,
+	// Once cells, skipped while uninitialized like `None`
+	Once,
+	// Interior mutability cells, printed as the value or a marker when it's unavailable
+	Cell,
+}
+
+// How the elements of a collection are printed by `#[debug(elements)]`
+#[derive(Clone, Copy)]
+pub enum Shape {
+	// `[a, b]`
+	List,
+	// `{a, b}`
+	Set,
+	// `{k: v}`
+	Map,
+	// Strings and types that aren't known to have `iter()`, their elements aren't shortened
+	Opaque,
+}
+
+const OPTION: TypeKind = TypeKind::Option;
+const LIST: TypeKind = TypeKind::Collection(Shape::List);
+const SET: TypeKind = TypeKind::Collection(Shape::Set);
+const MAP: TypeKind = TypeKind::Collection(Shape::Map);
+const OPAQUE: TypeKind = TypeKind::Collection(Shape::Opaque);
+const ONCE: TypeKind = TypeKind::Once;
+const CELL: TypeKind = TypeKind::Cell;
+
+// Recognized type: crates it's exported from, module path inside the crate with the type name
+struct KnownType {
+	crates: &'static [&'static str],
+	path: &'static [&'static str],
+	// Only match when written with generic arguments, so a user `struct Vec;` isn't recognized
+	generic: bool,
+	kind: TypeKind,
+}
+
+const STD_CORE: &[&str] = &["std", "core"];
+const STD_ALLOC: &[&str] = &["std", "alloc"];
+const STD: &[&str] = &["std"];
+
+// Adding a type here is enough to get it shortened
+const KNOWN_TYPES: &[KnownType] = &[
+	known(STD_CORE, &["option", "Option"], true, OPTION),
+	known(STD_ALLOC, &["vec", "Vec"], true, LIST),
+	known(STD_ALLOC, &["string", "String"], false, OPAQUE),
+	known(STD_ALLOC, &["collections", "VecDeque"], true, LIST),
+	known(STD_ALLOC, &["collections", "vec_deque", "VecDeque"], true, LIST),
+	known(STD_ALLOC, &["collections", "LinkedList"], true, LIST),
+	known(STD_ALLOC, &["collections", "linked_list", "LinkedList"], true, LIST),
+	known(STD_ALLOC, &["collections", "BinaryHeap"], true, LIST),
+	known(STD_ALLOC, &["collections", "binary_heap", "BinaryHeap"], true, LIST),
+	known(STD_ALLOC, &["collections", "BTreeMap"], true, MAP),
+	known(STD_ALLOC, &["collections", "btree_map", "BTreeMap"], true, MAP),
+	known(STD_ALLOC, &["collections", "BTreeSet"], true, SET),
+	known(STD_ALLOC, &["collections", "btree_set", "BTreeSet"], true, SET),
+	known(STD, &["collections", "HashMap"], true, MAP),
+	known(STD, &["collections", "hash_map", "HashMap"], true, MAP),
+	known(STD, &["collections", "HashSet"], true, SET),
+	known(STD, &["collections", "hash_set", "HashSet"], true, SET),
+	known(STD_CORE, &["cell", "OnceCell"], true, ONCE),
+	known(STD, &["sync", "OnceLock"], true, ONCE),
+	known(STD_CORE, &["cell", "Cell"], true, CELL),
+	known(STD_CORE, &["cell", "RefCell"], true, CELL),
+	known(STD, &["sync", "Mutex"], true, CELL),
+	known(STD, &["sync", "RwLock"], true, CELL),
+	#[cfg(feature = "smallvec")]
+	known(&["smallvec"], &["SmallVec"], true, LIST),
+	#[cfg(feature = "arrayvec")]
+	known(&["arrayvec"], &["ArrayVec"], true, LIST),
+	#[cfg(feature = "arrayvec")]
+	known(&["arrayvec"], &["ArrayString"], true, OPAQUE),
+	#[cfg(feature = "indexmap")]
+	known(&["indexmap"], &["IndexMap"], true, MAP),
+	#[cfg(feature = "indexmap")]
+	known(&["indexmap"], &["map", "IndexMap"], true, MAP),
+	#[cfg(feature = "indexmap")]
+	known(&["indexmap"], &["IndexSet"], true, SET),
+	#[cfg(feature = "indexmap")]
+	known(&["indexmap"], &["set", "IndexSet"], true, SET),
+	#[cfg(feature = "hashbrown")]
+	known(&["hashbrown"], &["HashMap"], true, MAP),
+	#[cfg(feature = "hashbrown")]
+	known(&["hashbrown"], &["hash_map", "HashMap"], true, MAP),
+	#[cfg(feature = "hashbrown")]
+	known(&["hashbrown"], &["HashSet"], true, SET),
+	#[cfg(feature = "hashbrown")]
+	known(&["hashbrown"], &["hash_set", "HashSet"], true, SET),
+	#[cfg(feature = "bytes")]
+	known(&["bytes"], &["Bytes"], false, OPAQUE),
+	#[cfg(feature = "bytes")]
+	known(&["bytes"], &["BytesMut"], false, OPAQUE),
+	#[cfg(feature = "heapless")]
+	known(&["heapless"], &["Vec"], true, LIST),
+	#[cfg(feature = "heapless")]
+	known(&["heapless"], &["vec", "Vec"], true, LIST),
+];
+
+// Transparent pointers, their fields are shortened by the pointee type
+const POINTERS: &[KnownType] = &[
+	pointer(STD_ALLOC, &["boxed", "Box"]),
+	pointer(STD_ALLOC, &["rc", "Rc"]),
+	pointer(STD_ALLOC, &["sync", "Arc"]),
+	pointer(STD_ALLOC, &["borrow", "Cow"]),
+];
+
+// `Pin<P>` derefs to the pointee of `P`
+const PIN: KnownType = pointer(STD_CORE, &["pin", "Pin"]);
+
+const fn known(
+	crates: &'static [&'static str],
+	path: &'static [&'static str],
+	generic: bool,
+	kind: TypeKind,
+) -> KnownType {
+	KnownType { crates, path, generic, kind }
+}
+
+// Pointers aren't shortened by themselves, so the kind is never used
+const fn pointer(crates: &'static [&'static str], path: &'static [&'static str]) -> KnownType {
+	known(crates, path, true, OPAQUE)
+}
+
+impl KnownType {
+	// The type may be written as its full path with optional leading `::`
+	// (`::std::collections::HashMap`), or as any suffix of the path inside the crate
+	// imported with `use` (`HashMap`, `collections::HashMap`)
+	fn matches(&self, path: &Path) -> bool {
+		let Some(last) = path.segments.last()
+		else {
+			return false;
+		};
+		if self.generic != matches!(last.arguments, PathArguments::AngleBracketed(_)) {
+			return false;
+		}
+
+		let names_match = |segments: &[&str]| {
+			segments.len() == path.segments.len()
+				&& path.segments.iter().zip(segments).all(|(seg, name)| seg.ident == name)
+		};
+		let full = self.crates.iter().any(|krate| {
+			let first = path.segments.first().is_some_and(|seg| seg.ident == krate);
+			first && names_match(&[&[*krate], self.path].concat())
+		});
+		let imported = path.leading_colon.is_none()
+			&& path.segments.len() <= self.path.len()
+			&& names_match(&self.path[self.path.len() - path.segments.len()..]);
+		full || imported
+	}
+}
+
+impl TypeKind {
+	// Recognizes the field type by its path, slices and arrays are lists
+	pub fn of(ty: &Type) -> Option<Self> {
+		if let Some(pointee) = pointee(ty) {
+			return Self::of(pointee);
+		}
+		match ty {
+			Type::Slice(_) | Type::Array(_) => Some(LIST),
+			Type::Path(TypePath { qself: None, path }) if path.is_ident("str") => Some(OPAQUE),
+			Type::Path(TypePath { qself: None, path }) => {
+				KNOWN_TYPES.iter().find(|known| known.matches(path)).map(|known| known.kind)
+			}
+			_ => None,
+		}
+	}
+}
+
+// Pointee of a reference or a transparent pointer type, like `T` in `&mut T`, `Arc<T>`
+// or `Pin<Box<T>>`
+pub fn pointee(ty: &Type) -> Option<&Type> {
+	let path = match ty {
+		Type::Reference(reference) => return Some(&reference.elem),
+		Type::Path(TypePath { qself: None, path }) => path,
+		_ => return None,
+	};
+	if PIN.matches(path) {
+		return first_type_arg(ty).and_then(pointee);
+	}
+	match POINTERS.iter().any(|known| known.matches(path)) {
+		true => first_type_arg(ty),
+		false => None,
+	}
+}
+
+// Type behind all references and transparent pointers
+pub fn peel_pointers(ty: &Type) -> &Type {
+	match pointee(ty) {
+		Some(pointee) => peel_pointers(pointee),
+		None => ty,
+	}
+}
+
+// Types of the elements printed by `#[debug(elements)]` through options and pointers:
+// the map key if any, and the element or the map value
+pub fn element_types(ty: &Type) -> Option<(Option<&Type>, &Type)> {
+	if let Some(pointee) = pointee(ty) {
+		return element_types(pointee);
+	}
+	let args = match ty {
+		Type::Slice(slice) => return Some((None, &slice.elem)),
+		Type::Array(array) => return Some((None, &array.elem)),
+		Type::Path(_) => type_args(ty),
+		_ => return None,
+	};
+	match TypeKind::of(ty)? {
+		TypeKind::Option => element_types(args.first()?),
+		TypeKind::Collection(Shape::List | Shape::Set) => match args.first()? {
+			// `SmallVec<[T; N]>`
+			array @ Type::Array(_) => element_types(array),
+			element => Some((None, element)),
+		},
+		TypeKind::Collection(Shape::Map) => Some((Some(args.first()?), args.get(1)?)),
+		_ => None,
+	}
+}
+
+// First generic type argument of the last path segment, like `T` in `Box<T>`
+pub fn first_type_arg(ty: &Type) -> Option<&Type> {
+	let Type::Path(TypePath { qself: None, path }) = ty
+	else {
+		return None;
+	};
+	let PathArguments::AngleBracketed(args) = &path.segments.last()?.arguments
+	else {
+		return None;
+	};
+	args.args.iter().find_map(|arg| match arg {
+		GenericArgument::Type(ty) => Some(ty),
+		_ => None,
+	})
+}
+
+// Generic type arguments of the last path segment
+fn type_args(ty: &Type) -> Vec<&Type> {
+	let Type::Path(TypePath { qself: None, path }) = ty
+	else {
+		return Vec::new();
+	};
+	let Some(PathArguments::AngleBracketed(args)) = path.segments.last().map(|seg| &seg.arguments)
+	else {
+		return Vec::new();
+	};
+	args.args
+		.iter()
+		.filter_map(|arg| match arg {
+			GenericArgument::Type(ty) => Some(ty),
+			_ => None,
+		})
+		.collect()
+}
@@ -0,0 +1,115 @@
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use short_debug::{ShortDebug, ShortValue};
+
+type Maybe<T> = Option<T>;
+type Bytes = Vec<u8>;
+
+#[derive(Debug)]
+struct Uuid(u8);
+
+// Not a `ShortValue` until it implements it
+#[derive(Debug)]
+struct Opaque(u8);
+
+impl ShortValue for Opaque {
+	type Inner = Self;
+
+	fn short_value(&self) -> Option<&Self> {
+		Some(self)
+	}
+}
+
+#[derive(ShortDebug)]
+struct Wrapper<T> {
+	#[debug(auto)]
+	inner: T,
+	n: u8,
+}
+
+#[derive(ShortDebug)]
+#[debug(auto)]
+struct Aliases<'a> {
+	maybe: Maybe<u8>,
+	bytes: Bytes,
+	id: Uuid,
+	str: &'a str,
+	wrapper: Wrapper<Option<u8>>,
+	maybe_id: Option<Uuid>,
+}
+
+#[test]
+fn auto_shortens_generic_fields() {
+	assert_eq!(format!("{:?}", Wrapper { inner: None::<u8>, n: 1 }), "Wrapper { n: 1 }");
+	assert_eq!(format!("{:?}", Wrapper { inner: Some(2u8), n: 1 }), "Wrapper { inner: 2, n: 1 }");
+	assert_eq!(format!("{:?}", Wrapper { inner: String::new(), n: 1 }), "Wrapper { n: 1 }");
+	assert_eq!(format!("{:?}", Wrapper { inner: vec![1u8], n: 1 }), "Wrapper { inner: [1], n: 1 }");
+}
+
+#[test]
+fn auto_shortens_aliases_and_keeps_other_types() {
+	let aliases = Aliases {
+		maybe: None,
+		bytes: vec![],
+		id: Uuid(1),
+		str: "",
+		wrapper: Wrapper { inner: Some(1), n: 2 },
+		maybe_id: None,
+	};
+	assert_eq!(
+		format!("{aliases:?}"),
+		"Aliases { id: Uuid(1), wrapper: Wrapper { inner: 1, n: 2 } }"
+	);
+	let aliases = Aliases { maybe: Some(1), bytes: vec![2], maybe_id: Some(Uuid(2)), ..aliases };
+	assert_eq!(
+		format!("{aliases:?}"),
+		"Aliases { maybe: 1, bytes: [2], id: Uuid(1), wrapper: Wrapper { inner: 1, n: 2 }, \
+		 maybe_id: Uuid(2) }"
+	);
+}
+
+#[test]
+fn derived_types_are_short_values() {
+	let wrapper = Wrapper { inner: Wrapper { inner: None::<u8>, n: 1 }, n: 2 };
+	assert_eq!(format!("{wrapper:?}"), "Wrapper { inner: Wrapper { n: 1 }, n: 2 }");
+}
+
+#[test]
+fn nested_and_empty_options_are_skipped() {
+	assert_eq!(
+		format!("{:?}", Wrapper { inner: Some(Vec::<u8>::new()), n: 1 }),
+		"Wrapper { n: 1 }"
+	);
+	assert_eq!(format!("{:?}", Wrapper { inner: Some(None::<u8>), n: 1 }), "Wrapper { n: 1 }");
+	assert_eq!(
+		format!("{:?}", Wrapper { inner: Some(Some(2u8)), n: 1 }),
+		"Wrapper { inner: 2, n: 1 }"
+	);
+}
+
+#[test]
+fn std_types_are_short_values() {
+	let wrapper = Wrapper { inner: Duration::from_secs(1), n: 1 };
+	assert_eq!(format!("{wrapper:?}"), "Wrapper { inner: 1s, n: 1 }");
+	assert_eq!(
+		format!("{:?}", Wrapper { inner: (1, "a"), n: 1 }),
+		r#"Wrapper { inner: (1, "a"), n: 1 }"#
+	);
+	assert_eq!(format!("{:?}", Wrapper { inner: PathBuf::new(), n: 1 }), "Wrapper { n: 1 }");
+	assert_eq!(
+		format!("{:?}", Wrapper { inner: Some(PathBuf::from("a")), n: 1 }),
+		r#"Wrapper { inner: "a", n: 1 }"#
+	);
+}
+
+#[test]
+fn other_types_implement_short_value() {
+	assert_eq!(
+		format!("{:?}", Wrapper { inner: Opaque(1), n: 1 }),
+		"Wrapper { inner: Opaque(1), n: 1 }"
+	);
+	assert_eq!(format!("{:?}", Wrapper { inner: None::<Opaque>, n: 1 }), "Wrapper { n: 1 }");
+}
@@ -0,0 +1,68 @@
+#![allow(non_snake_case)]
+
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug)]
+#[debug(auto_redact("pin"))]
+struct Settings {
+	user: &'static str,
+	password: &'static str,
+	db_password: &'static str,
+	apiKey: Option<&'static str>,
+	APIKey: &'static str,
+	access_token: Option<&'static str>,
+	refresh_token: Option<&'static str>,
+	secret_key: &'static str,
+	password_hash: &'static str,
+	api_key_id: u8,
+	card_pin: u16,
+	tokenizer: &'static str,
+	max_tokens: u32,
+	token_count: u32,
+	secretary: &'static str,
+	spin: u8,
+	#[debug(format = "{}")]
+	secret: u8,
+}
+
+#[derive(ShortDebug)]
+#[debug(auto_redact)]
+enum Login {
+	Password { password: u8 },
+	Guest(u8),
+}
+
+#[test]
+fn sensitive_names_are_redacted() {
+	let settings = Settings {
+		user: "u",
+		password: "p",
+		db_password: "p",
+		apiKey: Some("k"),
+		APIKey: "k",
+		access_token: None,
+		refresh_token: Some("t"),
+		secret_key: "k",
+		password_hash: "h",
+		api_key_id: 1,
+		card_pin: 1234,
+		tokenizer: "bpe",
+		max_tokens: 1,
+		token_count: 2,
+		secretary: "s",
+		spin: 3,
+		secret: 4,
+	};
+	assert_eq!(
+		format!("{settings:?}"),
+		"Settings { user: \"u\", password: ***, db_password: ***, apiKey: ***, APIKey: ***, \
+		 refresh_token: ***, secret_key: ***, password_hash: ***, api_key_id: ***, card_pin: ***, \
+		 tokenizer: \"bpe\", max_tokens: 1, token_count: ***, secretary: \"s\", spin: 3, secret: 4 }"
+	);
+}
+
+#[test]
+fn sensitive_names_in_variants() {
+	assert_eq!(format!("{:?}", Login::Password { password: 1 }), "Password { password: *** }");
+	assert_eq!(format!("{:?}", Login::Guest(1)), "Guest(1)");
+}
@@ -0,0 +1,133 @@
+use std::cell::{Cell, OnceCell, RefCell};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug)]
+struct Cells<T> {
+	cell: std::cell::Cell<u8>,
+	ref_cell: std::cell::RefCell<Vec<u8>>,
+	mutex: Arc<std::sync::Mutex<Option<u8>>>,
+	rw_lock: std::sync::RwLock<u8>,
+	once: std::cell::OnceCell<u8>,
+	once_lock: std::sync::OnceLock<Vec<u8>>,
+	generic: std::cell::RefCell<T>,
+	generic_once: std::sync::OnceLock<Option<T>>,
+	#[debug(auto)]
+	auto: std::cell::OnceCell<u8>,
+}
+
+fn cells() -> Cells<u8> {
+	Cells {
+		cell: Cell::new(1),
+		ref_cell: RefCell::default(),
+		mutex: Arc::new(Mutex::new(Some(1))),
+		rw_lock: RwLock::new(2),
+		once: OnceCell::new(),
+		once_lock: OnceLock::new(),
+		generic: RefCell::new(3),
+		generic_once: OnceLock::new(),
+		auto: OnceCell::new(),
+	}
+}
+
+#[test]
+fn cells_print_their_values() {
+	assert_eq!(format!("{:?}", cells()), "Cells { cell: 1, mutex: 1, rw_lock: 2, generic: 3 }");
+}
+
+#[test]
+fn initialized_once_cells_are_printed() {
+	let cells = cells();
+	cells.once.set(1).unwrap();
+	cells.once_lock.set(vec![2]).unwrap();
+	cells.generic_once.set(Some(9)).unwrap();
+	cells.auto.set(7).unwrap();
+	assert_eq!(
+		format!("{cells:?}"),
+		"Cells { cell: 1, mutex: 1, rw_lock: 2, once: 1, once_lock: [2], generic: 3, \
+		 generic_once: 9, auto: 7 }"
+	);
+}
+
+#[test]
+fn busy_cells_print_a_marker() {
+	let cells = cells();
+	let _borrow = cells.generic.borrow_mut();
+	let _lock = cells.mutex.lock().unwrap();
+	assert_eq!(
+		format!("{cells:?}"),
+		"Cells { cell: 1, mutex: <locked>, rw_lock: 2, generic: <borrowed> }"
+	);
+}
+
+// Types named like std cells that don't implement `ShortCell` or `ShortOnce`
+mod grid {
+	#[derive(Debug)]
+	pub struct Cell<T>(pub T);
+
+	#[derive(Debug)]
+	pub struct Mutex<T>(pub T);
+
+	#[derive(Debug)]
+	pub struct OnceCell<T>(pub Option<T>);
+}
+
+mod imported {
+	use short_debug::ShortDebug;
+
+	use super::grid::{Cell, Mutex, OnceCell};
+
+	#[derive(ShortDebug)]
+	pub struct Imported {
+		pub cell: Cell<u8>,
+		pub mutex: Mutex<u8>,
+		pub once: OnceCell<u8>,
+		pub std_cell: std::cell::Cell<u8>,
+		pub std_mutex: super::Mutex<u8>,
+	}
+}
+
+mod imported_std {
+	use std::cell::RefCell;
+	use std::sync::{Mutex, OnceLock};
+
+	use short_debug::ShortDebug;
+
+	#[derive(ShortDebug)]
+	pub struct ImportedStd {
+		pub cell: RefCell<u8>,
+		pub mutex: Mutex<u8>,
+		pub once: OnceLock<u8>,
+		pub unset: OnceLock<u8>,
+	}
+}
+
+#[test]
+fn imported_std_cells_are_shortened() {
+	let imported = imported_std::ImportedStd {
+		cell: RefCell::new(1),
+		mutex: Mutex::new(2),
+		once: OnceLock::from(3),
+		unset: OnceLock::new(),
+	};
+	assert_eq!(format!("{imported:?}"), "ImportedStd { cell: 1, mutex: 2, once: 3 }");
+	let _borrow = imported.cell.borrow_mut();
+	assert_eq!(format!("{imported:?}"), "ImportedStd { cell: <borrowed>, mutex: 2, once: 3 }");
+}
+
+#[test]
+fn lookalike_cell_names_are_printed_as_is() {
+	let imported = imported::Imported {
+		cell: grid::Cell(1),
+		mutex: grid::Mutex(2),
+		once: grid::OnceCell(None),
+		std_cell: Cell::new(3),
+		std_mutex: Mutex::new(4),
+	};
+	assert_eq!(
+		format!("{imported:?}"),
+		"Imported { cell: Cell(1), mutex: Mutex(2), once: OnceCell(None), std_cell: 3, \
+		 std_mutex: Mutex { data: 4, poisoned: false, .. } }"
+	);
+}
@@ -0,0 +1,65 @@
+use std::collections::*;
+
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug)]
+struct Collections<'a> {
+	string: String,
+	str: &'a str,
+	boxed_str: Box<str>,
+	hash_map: HashMap<u8, u8>,
+	btree_map: std::collections::btree_map::BTreeMap<u8, u8>,
+	hash_set: HashSet<u8>,
+	btree_set: BTreeSet<u8>,
+	deque: VecDeque<u8>,
+	heap: BinaryHeap<u8>,
+	list: LinkedList<u8>,
+	slice: &'a [u8],
+	boxed_slice: Box<[u8]>,
+	n: u8,
+}
+
+#[test]
+fn empty_collections_are_skipped() {
+	let collections = Collections {
+		string: String::new(),
+		str: "",
+		boxed_str: "".into(),
+		hash_map: HashMap::new(),
+		btree_map: BTreeMap::new(),
+		hash_set: HashSet::new(),
+		btree_set: BTreeSet::new(),
+		deque: VecDeque::new(),
+		heap: BinaryHeap::new(),
+		list: LinkedList::new(),
+		slice: &[],
+		boxed_slice: Box::new([]),
+		n: 1,
+	};
+	assert_eq!(format!("{collections:?}"), "Collections { n: 1 }");
+}
+
+#[test]
+fn non_empty_collections_are_printed() {
+	let collections = Collections {
+		string: "a".into(),
+		str: "b",
+		boxed_str: "c".into(),
+		hash_map: [(1, 1)].into(),
+		btree_map: [(1, 1)].into(),
+		hash_set: [1].into(),
+		btree_set: [1].into(),
+		deque: [1].into(),
+		heap: [1].into(),
+		list: [1].into(),
+		slice: &[1],
+		boxed_slice: Box::new([1]),
+		n: 1,
+	};
+	assert_eq!(
+		format!("{collections:?}"),
+		"Collections { string: \"a\", str: \"b\", boxed_str: \"c\", hash_map: {1: 1}, \
+		 btree_map: {1: 1}, hash_set: {1}, btree_set: {1}, deque: [1], heap: [1], list: [1], \
+		 slice: [1], boxed_slice: [1], n: 1 }"
+	);
+}
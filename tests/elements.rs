@@ -0,0 +1,104 @@
+#![allow(dead_code)]
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug)]
+struct Batch<'a> {
+	#[debug(elements)]
+	list: Vec<Option<u8>>,
+	#[debug(elements(drop_none))]
+	dropped: Vec<Option<u8>>,
+	#[debug(elements(drop_none))]
+	map: BTreeMap<u8, Option<u8>>,
+	#[debug(elements)]
+	set: BTreeSet<Option<u8>>,
+	#[debug(elements(drop_none))]
+	slice: &'a [Option<u8>],
+	#[debug(elements)]
+	option: Option<Vec<Option<u8>>>,
+}
+
+#[derive(ShortDebug)]
+#[debug(elements(drop_none))]
+struct Container {
+	nested: Vec<Option<Option<u8>>>,
+	lists: Vec<Vec<u8>>,
+}
+
+#[derive(Debug)]
+struct Uuid(u8);
+
+#[derive(ShortDebug)]
+#[debug(elements)]
+struct Empty {
+	strings: Vec<&'static str>,
+	map: BTreeMap<u8, String>,
+	deep: Vec<Option<Option<Option<u8>>>>,
+	ids: Vec<Option<Uuid>>,
+}
+
+#[derive(ShortDebug)]
+struct Generic<'a, T, K> {
+	#[debug(elements)]
+	list: Vec<T>,
+	#[debug(elements)]
+	map: Option<BTreeMap<K, T>>,
+	#[debug(elements)]
+	slice: &'a [T],
+}
+
+#[test]
+fn elements_are_shortened() {
+	let batch = Batch {
+		list: vec![Some(1), None],
+		dropped: vec![Some(1), None, Some(3), None],
+		map: [(1, Some(1)), (2, None)].into(),
+		set: [None, Some(1)].into(),
+		slice: &[None],
+		option: Some(vec![None, Some(2)]),
+	};
+	assert_eq!(
+		format!("{batch:?}"),
+		"Batch { list: [1, None], dropped: [1, 3, <2 none>], map: {1: 1, ..}, set: {None, 1}, \
+		 slice: [<1 none>], option: [None, 2] }"
+	);
+}
+
+#[test]
+fn container_elements_apply_to_all_collections() {
+	let container =
+		Container { nested: vec![Some(Some(1)), Some(None), None], lists: vec![vec![], vec![1]] };
+	assert_eq!(format!("{container:?}"), "Container { nested: [1, <2 none>], lists: [[], [1]] }");
+}
+
+#[test]
+fn empty_elements_are_printed() {
+	let empty = Empty {
+		strings: vec!["", "x"],
+		map: [(1, String::new())].into(),
+		deep: vec![Some(Some(Some(1))), Some(Some(None))],
+		ids: vec![Some(Uuid(1)), None],
+	};
+	assert_eq!(
+		format!("{empty:?}"),
+		r#"Empty { strings: ["", "x"], map: {1: ""}, deep: [1, None], ids: [Uuid(1), None] }"#
+	);
+}
+
+#[test]
+fn generic_elements() {
+	let generic =
+		Generic { list: vec![Some(1)], map: Some([("k", None)].into()), slice: &[None, Some(2)] };
+	assert_eq!(
+		format!("{generic:?}"),
+		r#"Generic { list: [1], map: {"k": None}, slice: [None, 2] }"#
+	);
+	let generic = Generic {
+		list: vec![Some(vec![])],
+		map: Some([("k", Some(vec![1]))].into()),
+		slice: &[None],
+	};
+	assert_eq!(format!("{generic:?}"), r#"Generic { list: [[]], map: {"k": [1]}, slice: [None] }"#);
+}
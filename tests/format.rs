@@ -0,0 +1,28 @@
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug)]
+struct Reading {
+	#[debug(format = "{:.3}")]
+	value: f64,
+	#[debug(format = "{:#010x}")]
+	flags: Option<u32>,
+	#[debug(format = "{:?}!")]
+	samples: Vec<u8>,
+	#[debug(format = "{} units")]
+	unit: u8,
+}
+
+#[test]
+fn template_formats_field() {
+	let reading = Reading { value: 1.23456, flags: Some(255), samples: vec![1], unit: 2 };
+	assert_eq!(
+		format!("{reading:?}"),
+		"Reading { value: 1.235, flags: 0x000000ff, samples: [1]!, unit: 2 units }"
+	);
+}
+
+#[test]
+fn template_keeps_none_and_empty_skipping() {
+	let reading = Reading { value: 1.0, flags: None, samples: vec![], unit: 0 };
+	assert_eq!(format!("{reading:?}"), "Reading { value: 1.000, unit: 0 units }");
+}
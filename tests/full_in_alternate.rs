@@ -0,0 +1,136 @@
+#![allow(dead_code)]
+
+use std::fmt;
+
+use short_debug::ShortDebug;
+
+fn hex(value: &u32, fmt: &mut fmt::Formatter) -> fmt::Result {
+	write!(fmt, "0x{value:x}")
+}
+
+#[derive(ShortDebug)]
+#[debug(full_in_alternate, rename_all = "camelCase", auto_redact, count_skipped)]
+struct Account {
+	user_id: Option<u8>,
+	tags: Vec<u8>,
+	password: String,
+	#[debug(skip)]
+	hidden: u8,
+	#[debug(format = "{:#x}")]
+	flags: u8,
+}
+
+#[derive(ShortDebug)]
+#[debug(full_in_alternate)]
+struct Formatted {
+	#[debug(format = "{:#x}")]
+	format: Option<u32>,
+	#[debug(with = hex)]
+	with: Option<Option<u32>>,
+	#[debug(redact(last = 2))]
+	redact: Option<String>,
+	#[debug(format = "{:?}!")]
+	list: Vec<u8>,
+	#[debug(format = "{:#x}")]
+	cell: std::cell::RefCell<Option<u32>>,
+}
+
+fn hex_u8(value: &u8, fmt: &mut fmt::Formatter) -> fmt::Result {
+	write!(fmt, "0x{value:x}")
+}
+
+#[derive(ShortDebug)]
+#[debug(auto, full_in_alternate)]
+struct Wrapped {
+	#[debug(with = hex_u8)]
+	option: Option<u8>,
+}
+
+#[derive(ShortDebug)]
+#[debug(full_in_alternate)]
+struct Locked {
+	#[debug(with = hex_u8)]
+	mutex: Option<std::sync::Mutex<u8>>,
+	#[debug(with = hex_u8)]
+	once: std::sync::OnceLock<u8>,
+}
+
+#[derive(ShortDebug)]
+#[debug(full_in_alternate, qualified)]
+enum Event<T> {
+	#[debug(transparent)]
+	Value(Option<T>),
+	#[debug(skip)]
+	Skipped(u8),
+	Empty,
+}
+
+#[test]
+fn short_output_without_alternate() {
+	let account =
+		Account { user_id: None, tags: vec![], password: "p".into(), hidden: 1, flags: 255 };
+	assert_eq!(format!("{account:?}"), "Account { password: ***, flags: 0xff, ..3 hidden }");
+	assert_eq!(format!("{:?}", Event::Value(Some(1))), "1");
+	assert_eq!(format!("{:?}", Event::<u8>::Skipped(1)), "Event::Skipped");
+}
+
+#[test]
+fn full_output_with_alternate() {
+	let account =
+		Account { user_id: None, tags: vec![], password: "p".into(), hidden: 1, flags: 255 };
+	assert_eq!(
+		format!("{account:#?}"),
+		"Account {\n    user_id: None,\n    tags: [],\n    password: ***,\n    flags: 0xff,\n    ..\n}"
+	);
+	assert_eq!(format!("{:#?}", Event::Value(Some(1))), "Value(\n    Some(\n        1,\n    ),\n)");
+	assert_eq!(format!("{:#?}", Event::<u8>::Skipped(1)), "Skipped(..)");
+	assert_eq!(format!("{:#?}", Event::<u8>::Empty), "Empty");
+}
+
+#[test]
+fn formatters_apply_inside_options_and_cells() {
+	let formatted = Formatted {
+		format: Some(255),
+		with: Some(Some(16)),
+		redact: Some("secret".into()),
+		list: vec![1],
+		cell: Some(17).into(),
+	};
+	assert_eq!(
+		format!("{formatted:#?}"),
+		"Formatted {\n    format: Some(\n        0xff,\n    ),\n    with: Some(\n        Some(\n            0x10,\n        ),\n    ),\n    redact: Some(\n        ***et,\n    ),\n    list: [1]!,\n    cell: RefCell {\n        value: Some(\n            0x11,\n        ),\n    },\n}"
+	);
+}
+
+#[test]
+fn none_and_empty_are_kept_with_formatters() {
+	let formatted =
+		Formatted { format: None, with: Some(None), redact: None, list: vec![], cell: None.into() };
+	assert_eq!(
+		format!("{formatted:#?}"),
+		"Formatted {\n    format: None,\n    with: Some(\n        None,\n    ),\n    redact: None,\n    list: []!,\n    cell: RefCell {\n        value: None,\n    },\n}"
+	);
+}
+
+#[test]
+fn option_wrapper_is_kept_with_container_auto() {
+	let wrapped = Wrapped { option: Some(10) };
+	assert_eq!(format!("{wrapped:?}"), "Wrapped { option: 0xa }");
+	assert_eq!(format!("{wrapped:#?}"), "Wrapped {\n    option: Some(\n        0xa,\n    ),\n}");
+}
+
+#[test]
+fn cell_wrappers_are_kept() {
+	let locked =
+		Locked { mutex: Some(std::sync::Mutex::new(11)), once: std::sync::OnceLock::new() };
+	assert_eq!(format!("{locked:?}"), "Locked { mutex: 0xb }");
+	assert_eq!(
+		format!("{locked:#?}"),
+		"Locked {\n    mutex: Some(\n        Mutex {\n            data: 0xb,\n            poisoned: false,\n            ..\n        },\n    ),\n    once: OnceLock(\n        <uninit>,\n    ),\n}"
+	);
+	let formatted =
+		Formatted { format: None, with: None, redact: None, list: vec![], cell: Some(1).into() };
+	let _borrow = formatted.cell.borrow_mut();
+	assert!(format!("{formatted:#?}")
+		.ends_with("cell: RefCell {\n        value: <borrowed>,\n    },\n}"));
+}
@@ -0,0 +1,47 @@
+use short_debug::ShortDebug;
+
+mod app {
+	#[derive(Debug)]
+	pub struct User;
+}
+
+#[derive(ShortDebug)]
+#[debug(show_generics)]
+struct Cache<'a, K, V, const SIZE: usize> {
+	key: Option<&'a K>,
+	values: Vec<V>,
+}
+
+#[derive(ShortDebug)]
+#[debug(show_generics(full))]
+struct Full<K>(Option<K>);
+
+#[derive(ShortDebug)]
+#[debug(show_generics, qualified)]
+enum Either<T> {
+	Left(T),
+	#[debug(skip)]
+	Right,
+}
+
+#[test]
+fn short_generic_names() {
+	let cache = Cache::<u64, app::User, 3> { key: None, values: vec![app::User] };
+	assert_eq!(format!("{cache:?}"), "Cache<u64, User, 3> { values: [User] }");
+	let cache = Cache::<Vec<app::User>, Option<&str>, 0> { key: None, values: vec![] };
+	assert_eq!(format!("{cache:?}"), "Cache<Vec<User>, Option<&str>, 0>");
+}
+
+#[test]
+fn full_generic_names() {
+	assert_eq!(
+		format!("{:?}", Full::<Vec<app::User>>(None)),
+		"Full<alloc::vec::Vec<generics::app::User>>"
+	);
+}
+
+#[test]
+fn generic_names_with_qualified_variants() {
+	assert_eq!(format!("{:?}", Either::Left(1u8)), "Either<u8>::Left(1)");
+	assert_eq!(format!("{:?}", Either::<u8>::Right), "Either<u8>::Right");
+}
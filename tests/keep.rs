@@ -0,0 +1,75 @@
+use std::cell::OnceCell;
+
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug, Default)]
+#[debug(skip_defaults)]
+struct Report {
+	#[debug(keep)]
+	error: Option<String>,
+	#[debug(keep)]
+	items: Vec<u8>,
+	count: Option<u8>,
+	#[debug(keep, auto)]
+	auto: Option<u8>,
+	#[debug(keep, auto, format = "{:#x}")]
+	hex: Option<u32>,
+	#[debug(keep_some_wrapper)]
+	wrapped: Option<Option<u8>>,
+}
+
+#[derive(ShortDebug)]
+#[debug(keep_none, keep_some_wrapper)]
+struct KeepNone(Option<Option<u8>>, Vec<u8>, Option<Vec<u8>>, std::cell::OnceCell<u8>);
+
+#[derive(ShortDebug)]
+#[debug(keep_empty)]
+struct KeepEmpty {
+	list: Vec<u8>,
+	none: Option<String>,
+	empty: Option<String>,
+}
+
+#[test]
+fn keep_prints_none_and_empty_fields() {
+	assert_eq!(
+		format!("{:?}", Report::default()),
+		"Report { error: None, items: [], auto: None, hex: None }"
+	);
+}
+
+#[test]
+fn keep_prints_values_shortened() {
+	let report = Report {
+		error: Some("e".into()),
+		items: vec![1],
+		count: Some(1),
+		auto: Some(2),
+		hex: Some(255),
+		wrapped: Some(Some(3)),
+	};
+	assert_eq!(
+		format!("{report:?}"),
+		"Report { error: \"e\", items: [1], count: 1, auto: 2, hex: 0xff, wrapped: Some(Some(3)) }"
+	);
+}
+
+#[test]
+fn keep_none_and_some_wrapper_on_container() {
+	let once = OnceCell::new();
+	assert_eq!(
+		format!("{:?}", KeepNone(None, vec![], Some(vec![]), OnceCell::new())),
+		"KeepNone(None, <uninit>)"
+	);
+	once.set(1).unwrap();
+	assert_eq!(
+		format!("{:?}", KeepNone(Some(None), vec![], Some(vec![1]), once)),
+		"KeepNone(Some(None), Some([1]), 1)"
+	);
+}
+
+#[test]
+fn keep_empty_on_container() {
+	let keep = KeepEmpty { list: vec![], none: None, empty: Some(String::new()) };
+	assert_eq!(format!("{keep:?}"), r#"KeepEmpty { list: [], empty: "" }"#);
+}
@@ -0,0 +1,81 @@
+#![allow(dead_code)]
+
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug)]
+#[debug(mark_skipped)]
+struct Marked {
+	a: u8,
+	b: Option<u8>,
+	#[debug(skip)]
+	c: u8,
+}
+
+#[derive(ShortDebug)]
+#[debug(count_skipped)]
+struct Counted {
+	a: u8,
+	b: Option<u8>,
+	list: Vec<u8>,
+	#[debug(skip_default)]
+	zero: u8,
+}
+
+#[derive(ShortDebug)]
+#[debug(count_skipped)]
+enum Event {
+	Tuple(u8, Option<u8>, Vec<u8>),
+	Unit,
+	Named { option: Option<u8> },
+}
+
+#[derive(ShortDebug)]
+#[debug(mark_skipped, placeholder)]
+struct Placeholder(u8, Option<u8>);
+
+#[derive(ShortDebug)]
+#[debug(count_skipped, anonymous)]
+struct Anonymous {
+	a: Option<u8>,
+}
+
+#[test]
+fn mark_skipped_ends_with_dots() {
+	assert_eq!(format!("{:?}", Marked { a: 1, b: None, c: 2 }), "Marked { a: 1, .. }");
+	assert_eq!(
+		format!("{:#?}", Marked { a: 1, b: Some(2), c: 3 }),
+		"Marked {\n    a: 1,\n    b: 2,\n    ..\n}"
+	);
+}
+
+#[test]
+fn count_skipped_ends_with_count() {
+	let counted = Counted { a: 1, b: None, list: vec![], zero: 0 };
+	assert_eq!(format!("{counted:?}"), "Counted { a: 1, ..3 hidden }");
+	let counted = Counted { a: 1, b: Some(1), list: vec![1], zero: 1 };
+	assert_eq!(format!("{counted:?}"), "Counted { a: 1, b: 1, list: [1], zero: 1 }");
+	assert_eq!(format!("{:?}", Event::Unit), "Unit");
+	assert_eq!(format!("{:?}", Event::Named { option: None }), "Named { ..1 hidden }");
+	assert_eq!(format!("{:?}", Anonymous { a: None }), "{ ..1 hidden }");
+}
+
+#[test]
+fn count_skipped_on_tuples() {
+	assert_eq!(format!("{:?}", Event::Tuple(1, None, vec![])), "Tuple(1, ..2 hidden)");
+	assert_eq!(format!("{:?}", Event::Tuple(1, Some(2), vec![3])), "Tuple(1, 2, [3])");
+	assert_eq!(format!("{:?}", Placeholder(1, None)), "Placeholder(1, _)");
+}
+
+#[test]
+fn count_skipped_alternate_has_no_trailing_comma() {
+	let counted = Counted { a: 1, b: None, list: vec![], zero: 0 };
+	assert_eq!(format!("{counted:#?}"), "Counted {\n    a: 1,\n    ..3 hidden\n}");
+	assert_eq!(
+		format!("{:#?}", Event::Tuple(1, None, vec![])),
+		"Tuple(\n    1,\n    ..2 hidden\n)"
+	);
+	assert_eq!(
+		format!("{:#?}", Event::Tuple(1, Some(2), vec![])),
+		"Tuple(\n    1,\n    2,\n    ..1 hidden\n)"
+	);
+}
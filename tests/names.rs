@@ -0,0 +1,78 @@
+#![allow(dead_code)]
+
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug)]
+#[debug(qualified)]
+enum Status {
+	Active,
+	#[debug(name = "Off")]
+	Inactive(u8),
+	#[debug(anonymous)]
+	Anonymous {
+		a: u8,
+		b: Option<u8>,
+	},
+	#[debug(skip)]
+	Skipped(u8),
+}
+
+#[derive(ShortDebug)]
+#[debug(qualified, name = "M", rename_all = "snake_case")]
+enum Mode {
+	ActiveMode,
+}
+
+#[derive(ShortDebug)]
+#[debug(name = "Renamed")]
+struct Original {
+	a: u8,
+}
+
+#[derive(ShortDebug)]
+#[debug(anonymous)]
+struct AnonymousStruct {
+	a: u8,
+	list: Vec<u8>,
+	original: Original,
+}
+
+#[derive(ShortDebug)]
+#[debug(anonymous)]
+struct AnonymousTuple(u8, u8);
+
+#[derive(ShortDebug)]
+#[debug(anonymous)]
+struct AnonymousUnit;
+
+#[test]
+fn qualified_variant_names() {
+	assert_eq!(format!("{:?}", Status::Active), "Status::Active");
+	assert_eq!(format!("{:?}", Status::Inactive(1)), "Status::Off(1)");
+	assert_eq!(format!("{:?}", Status::Skipped(1)), "Status::Skipped");
+	assert_eq!(format!("{:?}", Mode::ActiveMode), "M::active_mode");
+}
+
+#[test]
+fn name_overrides() {
+	assert_eq!(format!("{:?}", Original { a: 1 }), "Renamed { a: 1 }");
+	assert_eq!(format!("{:#?}", Original { a: 1 }), "Renamed {\n    a: 1,\n}");
+}
+
+#[test]
+fn anonymous_output() {
+	assert_eq!(format!("{:?}", Status::Anonymous { a: 1, b: None }), "{ a: 1 }");
+	let anonymous = AnonymousStruct { a: 1, list: vec![], original: Original { a: 2 } };
+	assert_eq!(format!("{anonymous:?}"), "{ a: 1, original: Renamed { a: 2 } }");
+	assert_eq!(format!("{:?}", AnonymousTuple(1, 2)), "(1, 2)");
+	assert_eq!(format!("{:?}", AnonymousUnit), "AnonymousUnit");
+}
+
+#[test]
+fn anonymous_alternate() {
+	let anonymous = AnonymousStruct { a: 1, list: vec![2], original: Original { a: 3 } };
+	assert_eq!(
+		format!("{anonymous:#?}"),
+		"{\n    a: 1,\n    list: [\n        2,\n    ],\n    original: Renamed {\n        a: 3,\n    },\n}"
+	);
+}
@@ -0,0 +1,52 @@
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug)]
+struct Nested {
+	list: Option<Vec<u8>>,
+	option: Option<Option<u8>>,
+	#[debug(keep_some_none)]
+	kept: Option<Option<Option<u8>>>,
+	string: Option<String>,
+	#[debug(format = "{:#x}")]
+	hex: Option<Option<u8>>,
+}
+
+#[derive(ShortDebug)]
+#[debug(placeholder)]
+struct Pair(Option<Option<u8>>, #[debug(keep_some_none)] Option<Option<u8>>);
+
+#[test]
+fn nested_options_are_flattened() {
+	let nested = Nested {
+		list: Some(vec![1]),
+		option: Some(Some(1)),
+		kept: Some(Some(Some(3))),
+		string: Some("x".into()),
+		hex: Some(Some(255)),
+	};
+	assert_eq!(
+		format!("{nested:?}"),
+		"Nested { list: [1], option: 1, kept: 3, string: \"x\", hex: 0xff }"
+	);
+}
+
+#[test]
+fn some_of_none_or_empty_is_skipped() {
+	let nested = Nested {
+		list: Some(vec![]),
+		option: Some(None),
+		kept: None,
+		string: Some(String::new()),
+		hex: Some(None),
+	};
+	assert_eq!(format!("{nested:?}"), "Nested");
+}
+
+#[test]
+fn keep_some_none_prints_innermost_none() {
+	let nested =
+		Nested { list: None, option: None, kept: Some(Some(None)), string: None, hex: None };
+	assert_eq!(format!("{nested:?}"), "Nested { kept: None }");
+	assert_eq!(format!("{:?}", Pair(Some(None), Some(None))), "Pair(_, None)");
+	assert_eq!(format!("{:?}", Pair(Some(Some(1)), None)), "Pair(1, _)");
+}
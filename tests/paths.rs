@@ -0,0 +1,95 @@
+use short_debug::ShortDebug;
+
+mod my {
+	#[derive(Debug)]
+	pub struct Option<T>(pub T);
+
+	#[derive(Debug)]
+	pub struct Vec;
+}
+
+type Maybe<T> = Option<T>;
+type Bytes = Vec<u8>;
+
+#[derive(ShortDebug)]
+struct Paths {
+	a: std::option::Option<u8>,
+	b: ::core::option::Option<u8>,
+	c: std::vec::Vec<u8>,
+	d: my::Option<u8>,
+	e: my::Vec,
+	#[debug(option)]
+	f: Maybe<u8>,
+	#[debug(collection)]
+	g: Bytes,
+	h: Maybe<u8>,
+}
+
+#[test]
+fn std_paths_and_hints_are_shortened() {
+	let paths = Paths {
+		a: None,
+		b: Some(1),
+		c: vec![],
+		d: my::Option(2),
+		e: my::Vec,
+		f: None,
+		g: vec![],
+		h: None,
+	};
+	assert_eq!(format!("{paths:?}"), "Paths { b: 1, d: Option(2), e: Vec, h: None }");
+}
+
+#[test]
+fn hinted_values_are_printed_unwrapped() {
+	let paths = Paths {
+		a: Some(1),
+		b: None,
+		c: vec![2],
+		d: my::Option(3),
+		e: my::Vec,
+		f: Some(4),
+		g: vec![5],
+		h: Some(6),
+	};
+	assert_eq!(
+		format!("{paths:?}"),
+		"Paths { a: 1, c: [2], d: Option(3), e: Vec, f: 4, g: [5], h: Some(6) }"
+	);
+}
+
+// A generic type named `Option` imported into scope
+mod imported {
+	use short_debug::ShortDebug;
+
+	use super::my::Option;
+
+	#[derive(ShortDebug)]
+	pub struct Imported<T> {
+		pub value: Option<u8>,
+		pub generic: Option<T>,
+		pub boxed: Box<Option<Vec<u8>>>,
+		pub nested: core::option::Option<Option<u8>>,
+		#[debug(format = "{:#x}")]
+		pub format: Option<u32>,
+		#[debug(redact)]
+		pub redact: Option<u8>,
+	}
+}
+
+#[test]
+fn imported_option_lookalike_is_printed_as_is() {
+	let imported = imported::Imported {
+		value: my::Option(1),
+		generic: my::Option("a"),
+		boxed: Box::new(my::Option(vec![])),
+		nested: Some(my::Option(2)),
+		format: my::Option(255),
+		redact: my::Option(3),
+	};
+	assert_eq!(
+		format!("{imported:?}"),
+		"Imported { value: Option(1), generic: Option(\"a\"), boxed: Option([]), \
+		 nested: Option(2), format: Option(255), redact: *** }"
+	);
+}
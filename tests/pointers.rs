@@ -0,0 +1,57 @@
+use std::borrow::Cow;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug)]
+struct Pointers<'a> {
+	arc: Arc<Option<u8>>,
+	boxed: Box<Option<Vec<u8>>>,
+	cow: Cow<'a, str>,
+	rc: Rc<Vec<u8>>,
+	pin: Pin<Box<Option<u8>>>,
+	boxed_str: Box<str>,
+	cow_slice: Cow<'a, [u8]>,
+	#[debug(elements(drop_none))]
+	elements: Arc<Vec<Option<u8>>>,
+	#[debug(auto)]
+	auto: Pin<Box<Option<u8>>>,
+}
+
+#[test]
+fn empty_pointees_are_skipped() {
+	let pointers = Pointers {
+		arc: Arc::new(None),
+		boxed: Box::new(Some(vec![])),
+		cow: "".into(),
+		rc: Rc::default(),
+		pin: Box::pin(None),
+		boxed_str: "".into(),
+		cow_slice: Cow::Borrowed(&[]),
+		elements: Arc::default(),
+		auto: Box::pin(None),
+	};
+	assert_eq!(format!("{pointers:?}"), "Pointers");
+}
+
+#[test]
+fn pointees_are_printed_unwrapped() {
+	let pointers = Pointers {
+		arc: Arc::new(Some(1)),
+		boxed: Box::new(Some(vec![2])),
+		cow: "x".into(),
+		rc: Rc::new(vec![3]),
+		pin: Box::pin(Some(4)),
+		boxed_str: "y".into(),
+		cow_slice: Cow::Owned(vec![5]),
+		elements: Arc::new(vec![None]),
+		auto: Box::pin(Some(6)),
+	};
+	assert_eq!(
+		format!("{pointers:?}"),
+		"Pointers { arc: 1, boxed: [2], cow: \"x\", rc: [3], pin: 4, boxed_str: \"y\", \
+		 cow_slice: [5], elements: [<1 none>], auto: 6 }"
+	);
+}
@@ -0,0 +1,107 @@
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug)]
+struct Credentials {
+	#[debug(redact)]
+	password: String,
+	#[debug(redact(last = 4))]
+	card: Option<String>,
+	#[debug(redact(last = 4))]
+	pin: String,
+	#[debug(redact(hash))]
+	token: String,
+	#[debug(redact(last = 64))]
+	long: String,
+}
+
+fn credentials() -> Credentials {
+	Credentials {
+		password: "hunter2".into(),
+		card: Some("1234567812345678".into()),
+		pin: "1234".into(),
+		token: "tok".into(),
+		long: "a".repeat(65),
+	}
+}
+
+#[test]
+fn redacted_fields_hide_value() {
+	let output = format!("{:?}", credentials());
+	assert!(output.starts_with("Credentials { password: ***, card: ***5678, pin: ***, token: ***#"));
+	assert!(!output.contains("hunter2"));
+}
+
+#[test]
+fn last_never_reveals_whole_value() {
+	let output = format!("{:?}", credentials());
+	assert!(output.ends_with(&format!("long: ***{} }}", "a".repeat(64))));
+}
+
+#[test]
+fn hash_is_stable() {
+	let token = |token: &str| {
+		let output = format!("{:?}", Credentials { token: token.into(), ..credentials() });
+		output.split("token: ").nth(1).unwrap().split(',').next().unwrap().to_owned()
+	};
+	assert_eq!(token("tok"), token("tok"));
+	assert_ne!(token("tok"), token("other"));
+}
+
+#[test]
+fn redacted_none_is_skipped() {
+	let output = format!("{:?}", Credentials { card: None, ..credentials() });
+	assert!(output.starts_with("Credentials { password: ***, pin: ***,"));
+}
+
+#[derive(ShortDebug)]
+struct Keys<T> {
+	#[debug(redact(last = 4))]
+	bytes: Vec<u8>,
+	#[debug(redact(last = 2))]
+	array: [u8; 4],
+	#[debug(redact(last = 2))]
+	generic: T,
+}
+
+#[test]
+fn last_uses_debug_without_display() {
+	let keys = Keys { bytes: vec![1, 2, 3], array: [4, 5, 6, 7], generic: 89 };
+	assert_eq!(format!("{keys:?}"), "Keys { bytes: ***, 3], array: ***7], generic: *** }");
+}
+
+// Glob imports like `use anyhow::*` bring an `Ok` function into scope
+mod shadowed {
+	#![allow(non_snake_case)]
+
+	pub mod prelude {
+		pub fn Ok<T>(value: T) -> anyhow_like::Result<T> {
+			anyhow_like::Result::Ok(value)
+		}
+
+		pub mod anyhow_like {
+			pub type Result<T> = core::result::Result<T, ()>;
+		}
+	}
+
+	use prelude::*;
+	use short_debug::ShortDebug;
+
+	#[derive(ShortDebug)]
+	pub struct Token {
+		#[debug(redact(last = 2))]
+		pub last: &'static str,
+		#[debug(redact(hash))]
+		pub hash: &'static str,
+	}
+
+	pub fn check() -> anyhow_like::Result<()> {
+		Ok(())
+	}
+}
+
+#[test]
+fn redacted_write_ignores_glob_imported_ok() {
+	let token = shadowed::Token { last: "abcd", hash: "x" };
+	assert!(format!("{token:?}").starts_with("Token { last: ***cd, hash: ***#"));
+	assert_eq!(shadowed::check(), Ok(()));
+}
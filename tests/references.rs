@@ -0,0 +1,61 @@
+use std::pin::Pin;
+
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug)]
+struct View<'a, T> {
+	tags: &'a [u8],
+	parent: &'a Option<u8>,
+	list: &'a Vec<u8>,
+	str: &'a str,
+	option: &'a mut Option<Vec<u8>>,
+	empty: [u8; 0],
+	array: [u8; 2],
+	nested: &'a &'a Option<u8>,
+	generic: &'a Option<T>,
+	#[debug(elements(drop_none))]
+	elements: &'a [Option<T>],
+	pin: Pin<&'a mut Option<u8>>,
+}
+
+#[test]
+fn empty_references_are_skipped() {
+	let (mut option, mut pin) = (Some(vec![]), None);
+	let view = View {
+		tags: &[],
+		parent: &None,
+		list: &vec![],
+		str: "",
+		option: &mut option,
+		empty: [],
+		array: [1, 2],
+		nested: &&None,
+		generic: &None::<u8>,
+		elements: &[None],
+		pin: Pin::new(&mut pin),
+	};
+	assert_eq!(format!("{view:?}"), "View { array: [1, 2], elements: [<1 none>] }");
+}
+
+#[test]
+fn references_are_printed_unwrapped() {
+	let (mut option, mut pin) = (Some(vec![1]), Some(4));
+	let view = View {
+		tags: &[1],
+		parent: &Some(2),
+		list: &vec![3],
+		str: "s",
+		option: &mut option,
+		empty: [],
+		array: [1, 2],
+		nested: &&Some(3),
+		generic: &Some(1u8),
+		elements: &[Some(2), None],
+		pin: Pin::new(&mut pin),
+	};
+	assert_eq!(
+		format!("{view:?}"),
+		"View { tags: [1], parent: 2, list: [3], str: \"s\", option: [1], array: [1, 2], \
+		 nested: 3, generic: 1, elements: [2, <1 none>], pin: 4 }"
+	);
+}
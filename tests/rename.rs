@@ -0,0 +1,62 @@
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug)]
+#[debug(rename_all = "camelCase")]
+struct Account {
+	user_id: u8,
+	#[debug(rename = "ID")]
+	id: u8,
+	r#type: u8,
+}
+
+#[derive(ShortDebug)]
+#[debug(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Message {
+	FooBar {
+		a_b: u8,
+	},
+	#[debug(rename_all = "kebab-case")]
+	Baz {
+		a_b: u8,
+	},
+	Tuple(u8),
+}
+
+#[derive(ShortDebug)]
+#[debug(rename_all = "PascalCase")]
+struct Pascal {
+	user_id: u8,
+}
+
+#[derive(ShortDebug)]
+#[debug(rename_all = "lowercase")]
+enum Lower {
+	FooBar,
+}
+
+#[derive(ShortDebug)]
+struct r#Raw {
+	r#type: u8,
+}
+
+#[test]
+fn rename_all_on_struct_fields() {
+	assert_eq!(
+		format!("{:?}", Account { user_id: 1, id: 2, r#type: 3 }),
+		"Account { userId: 1, ID: 2, type: 3 }"
+	);
+	assert_eq!(format!("{:?}", Pascal { user_id: 1 }), "Pascal { UserId: 1 }");
+}
+
+#[test]
+fn rename_all_on_variants_and_their_fields() {
+	assert_eq!(format!("{:?}", Message::FooBar { a_b: 1 }), "FOO_BAR { a_b: 1 }");
+	assert_eq!(format!("{:?}", Message::Baz { a_b: 1 }), "BAZ { a-b: 1 }");
+	assert_eq!(format!("{:?}", Message::Tuple(1)), "TUPLE(1)");
+	assert_eq!(format!("{:?}", Lower::FooBar), "foobar");
+}
+
+#[test]
+fn raw_identifiers_print_unprefixed() {
+	assert_eq!(format!("{:?}", Raw { r#type: 1 }), "Raw { type: 1 }");
+}
@@ -0,0 +1,56 @@
+#![allow(dead_code)]
+
+use std::fmt;
+
+use short_debug::{ShortDebug, ShortEmpty};
+
+struct Ids(Vec<u8>);
+
+impl fmt::Debug for Ids {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(fmt)
+	}
+}
+
+impl ShortEmpty for Ids {
+	fn is_short_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+}
+
+// A third-party map that happens to be named like a std collection,
+// but doesn't implement `ShortEmpty`
+mod hb {
+	#[derive(Debug)]
+	pub struct HashMap<K, V>(pub Vec<(K, V)>);
+
+	impl<K, V> HashMap<K, V> {
+		pub fn is_empty(&self) -> bool {
+			self.0.is_empty()
+		}
+	}
+}
+
+use hb::HashMap;
+
+#[derive(ShortDebug)]
+struct Index {
+	#[debug(collection)]
+	ids: Ids,
+	map: HashMap<u8, u8>,
+	maybe: Option<HashMap<u8, u8>>,
+}
+
+#[test]
+fn own_collection_uses_short_empty() {
+	let index = Index { ids: Ids(vec![]), map: HashMap(vec![(1, 2)]), maybe: None };
+	assert_eq!(format!("{index:?}"), "Index { map: HashMap([(1, 2)]) }");
+	let index = Index { ids: Ids(vec![1]), map: HashMap(vec![(1, 2)]), maybe: None };
+	assert_eq!(format!("{index:?}"), "Index { ids: [1], map: HashMap([(1, 2)]) }");
+}
+
+#[test]
+fn unknown_collection_is_printed_as_is() {
+	let index = Index { ids: Ids(vec![]), map: HashMap(vec![]), maybe: Some(HashMap(vec![])) };
+	assert_eq!(format!("{index:?}"), "Index { map: HashMap([]), maybe: HashMap([]) }");
+}
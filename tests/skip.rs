@@ -0,0 +1,47 @@
+#![allow(dead_code)]
+
+use short_debug::ShortDebug;
+
+struct NoDebug;
+
+#[derive(ShortDebug)]
+struct User {
+	id: u8,
+	#[debug(skip)]
+	handle: NoDebug,
+	name: Option<&'static str>,
+}
+
+#[derive(ShortDebug)]
+enum Event<T> {
+	Start(u8),
+	#[debug(skip)]
+	Internal {
+		payload: T,
+	},
+	Stop {
+		#[debug(skip)]
+		payload: T,
+		code: Option<u8>,
+	},
+}
+
+#[test]
+fn skipped_field_is_omitted() {
+	let user = User { id: 1, handle: NoDebug, name: Some("a") };
+	assert_eq!(format!("{user:?}"), r#"User { id: 1, name: "a" }"#);
+}
+
+#[test]
+fn skipped_variant_prints_only_its_name() {
+	assert_eq!(format!("{:?}", Event::Internal { payload: NoDebug }), "Internal");
+	assert_eq!(format!("{:?}", Event::<NoDebug>::Start(1)), "Start(1)");
+}
+
+#[test]
+fn skipped_generic_field_needs_no_debug() {
+	assert_eq!(
+		format!("{:?}", Event::Stop { payload: NoDebug, code: Some(2) }),
+		"Stop { code: 2 }"
+	);
+}
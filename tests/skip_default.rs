@@ -0,0 +1,57 @@
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug, Default, PartialEq)]
+enum Mode {
+	#[default]
+	Off,
+	On,
+}
+
+#[derive(ShortDebug)]
+struct Options<T> {
+	#[debug(skip_default)]
+	retries: u8,
+	timeout: u8,
+	#[debug(skip_default)]
+	mode: Mode,
+	#[debug(skip_default)]
+	extra: T,
+}
+
+#[derive(ShortDebug)]
+#[debug(skip_defaults)]
+struct Flags(u8, bool, String, Option<u8>);
+
+#[derive(ShortDebug, PartialEq)]
+#[debug(diff_default)]
+struct Config {
+	port: u16,
+	host: String,
+}
+
+impl Default for Config {
+	fn default() -> Self {
+		Config { port: 80, host: "localhost".into() }
+	}
+}
+
+#[test]
+fn skip_default_on_fields() {
+	let options = Options { retries: 0, timeout: 0, mode: Mode::Off, extra: 0u8 };
+	assert_eq!(format!("{options:?}"), "Options { timeout: 0 }");
+	let options = Options { retries: 1, timeout: 0, mode: Mode::On, extra: 1u8 };
+	assert_eq!(format!("{options:?}"), "Options { retries: 1, timeout: 0, mode: On, extra: 1 }");
+}
+
+#[test]
+fn skip_defaults_on_container() {
+	assert_eq!(format!("{:?}", Flags(0, false, String::new(), Some(0))), "Flags(0)");
+	assert_eq!(format!("{:?}", Flags(1, true, "x".into(), None)), r#"Flags(1, true, "x")"#);
+}
+
+#[test]
+fn diff_default_compares_with_container_default() {
+	assert_eq!(format!("{:?}", Config::default()), "Config");
+	let config = Config { port: 81, ..Config::default() };
+	assert_eq!(format!("{config:?}"), "Config { port: 81 }");
+}
@@ -0,0 +1,34 @@
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug)]
+struct Server {
+	#[debug(skip_if = Self::is_default_port)]
+	port: u16,
+	#[debug(skip_if = str::is_empty)]
+	host: String,
+	#[debug(skip_if = Option::is_none)]
+	name: Option<u8>,
+}
+
+impl Server {
+	fn is_default_port(port: &u16) -> bool {
+		*port == 80
+	}
+}
+
+#[derive(ShortDebug)]
+struct Label(#[debug(skip_if = str::is_empty)] String, u8);
+
+#[test]
+fn predicate_skips_field() {
+	let server = Server { port: 80, host: String::new(), name: None };
+	assert_eq!(format!("{server:?}"), "Server");
+	assert_eq!(format!("{:?}", Label(String::new(), 1)), "Label(1)");
+}
+
+#[test]
+fn predicate_keeps_field() {
+	let server = Server { port: 81, host: "h".into(), name: Some(1) };
+	assert_eq!(format!("{server:?}"), r#"Server { port: 81, host: "h", name: 1 }"#);
+	assert_eq!(format!("{:?}", Label("a".into(), 1)), r#"Label("a", 1)"#);
+}
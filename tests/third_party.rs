@@ -0,0 +1,53 @@
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug, Default)]
+struct Buffers {
+	small: smallvec::SmallVec<[u8; 4]>,
+	array: arrayvec::ArrayVec<u8, 4>,
+	array_string: arrayvec::ArrayString<4>,
+	index_map: indexmap::IndexMap<u8, u8>,
+	index_set: indexmap::IndexSet<u8>,
+	hash_map: hashbrown::HashMap<u8, u8>,
+	bytes: bytes::Bytes,
+	bytes_mut: bytes::BytesMut,
+	heapless: heapless::Vec<u8, 4>,
+	n: u8,
+}
+
+#[test]
+#[cfg(all(
+	feature = "smallvec",
+	feature = "arrayvec",
+	feature = "indexmap",
+	feature = "hashbrown",
+	feature = "bytes",
+	feature = "heapless"
+))]
+fn empty_collections_are_skipped_with_features() {
+	let mut buffers = Buffers::default();
+	assert_eq!(format!("{buffers:?}"), "Buffers { n: 0 }");
+	buffers.small.push(1);
+	buffers.bytes = bytes::Bytes::from_static(b"a");
+	buffers.heapless.push(2).unwrap();
+	assert_eq!(
+		format!("{buffers:?}"),
+		"Buffers { small: [1], bytes: b\"a\", heapless: [2], n: 0 }"
+	);
+}
+
+#[test]
+#[cfg(not(any(
+	feature = "smallvec",
+	feature = "arrayvec",
+	feature = "indexmap",
+	feature = "hashbrown",
+	feature = "bytes",
+	feature = "heapless"
+)))]
+fn collections_are_printed_as_is_without_features() {
+	assert_eq!(
+		format!("{:?}", Buffers::default()),
+		"Buffers { small: [], array: [], array_string: \"\", index_map: {}, index_set: {}, \
+		 hash_map: {}, bytes: b\"\", bytes_mut: b\"\", heapless: [], n: 0 }"
+	);
+}
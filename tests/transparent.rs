@@ -0,0 +1,62 @@
+use std::marker::PhantomData;
+
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug)]
+#[debug(transparent)]
+struct UserId(u64);
+
+#[derive(ShortDebug)]
+#[debug(transparent)]
+struct Name {
+	#[debug(skip)]
+	_marker: PhantomData<u8>,
+	name: Option<String>,
+}
+
+#[derive(ShortDebug)]
+enum Value {
+	#[debug(transparent)]
+	Int(i64),
+	#[debug(transparent)]
+	Str {
+		s: String,
+	},
+	#[debug(transparent)]
+	List(#[debug(skip_if = Vec::is_empty)] Vec<u8>),
+	Other(u8),
+}
+
+#[derive(ShortDebug)]
+#[debug(transparent)]
+enum Hex {
+	Byte(#[debug(format = "{:#x}")] u8),
+	Maybe(Option<u8>),
+}
+
+#[test]
+fn transparent_struct_prints_its_field() {
+	assert_eq!(format!("{:?}", UserId(5)), "5");
+	assert_eq!(format!("{:?}", Name { _marker: PhantomData, name: Some("n".into()) }), r#""n""#);
+}
+
+#[test]
+fn none_and_empty_inner_values_are_kept() {
+	assert_eq!(format!("{:?}", Name { _marker: PhantomData, name: None }), "None");
+	assert_eq!(format!("{:?}", Hex::Maybe(None)), "None");
+}
+
+#[test]
+fn skipped_inner_value_prints_the_name() {
+	assert_eq!(format!("{:?}", Value::List(vec![])), "List");
+}
+
+#[test]
+fn transparent_variants() {
+	assert_eq!(format!("{:?}", Value::Int(1)), "1");
+	assert_eq!(format!("{:?}", Value::Str { s: String::new() }), r#""""#);
+	assert_eq!(format!("{:?}", Value::List(vec![1])), "[1]");
+	assert_eq!(format!("{:?}", Value::Other(2)), "Other(2)");
+	assert_eq!(format!("{:?}", Hex::Byte(255)), "0xff");
+	assert_eq!(format!("{:#?}", Value::Int(3)), "3");
+}
@@ -0,0 +1,31 @@
+#![allow(dead_code)]
+
+use short_debug::ShortDebug;
+
+#[derive(ShortDebug)]
+struct Id(Option<u64>);
+
+#[derive(ShortDebug)]
+enum Message {
+	Data(Vec<u8>, Option<u8>, u8),
+	#[debug(placeholder)]
+	Padded(Vec<u8>, Option<u8>, #[debug(skip)] u8, u8),
+}
+
+#[derive(ShortDebug)]
+#[debug(placeholder)]
+struct Row(Option<u8>, Vec<u8>, u8);
+
+#[test]
+fn tuple_fields_are_shortened() {
+	assert_eq!(format!("{:?}", Id(None)), "Id");
+	assert_eq!(format!("{:?}", Id(Some(3))), "Id(3)");
+	assert_eq!(format!("{:?}", Message::Data(vec![], Some(1), 2)), "Data(1, 2)");
+}
+
+#[test]
+fn placeholder_keeps_positions() {
+	assert_eq!(format!("{:?}", Message::Padded(vec![], None, 1, 5)), "Padded(_, _, _, 5)");
+	assert_eq!(format!("{:?}", Row(None, vec![1], 2)), "Row(_, [1], 2)");
+	assert_eq!(format!("{:?}", Row(Some(1), vec![], 2)), "Row(1, _, 2)");
+}
@@ -0,0 +1,53 @@
+#![allow(dead_code)]
+
+use std::fmt;
+
+use short_debug::ShortDebug;
+
+struct NoDebug;
+
+fn hex(value: &u32, fmt: &mut fmt::Formatter) -> fmt::Result {
+	write!(fmt, "0x{value:x}")
+}
+
+fn len<T>(value: &[T], fmt: &mut fmt::Formatter) -> fmt::Result {
+	write!(fmt, "<{} items>", value.len())
+}
+
+#[derive(ShortDebug)]
+struct Packet {
+	#[debug(with = hex)]
+	id: u32,
+	#[debug(with = hex)]
+	parent: Option<u32>,
+	#[debug(with = Self::handle)]
+	handle: NoDebug,
+	#[debug(with = len)]
+	data: Vec<u8>,
+}
+
+impl Packet {
+	fn handle(_: &NoDebug, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.write_str("handle")
+	}
+}
+
+#[derive(ShortDebug)]
+struct Flags(#[debug(with = hex)] u32, u8);
+
+#[test]
+fn formatter_is_called_for_field() {
+	let packet = Packet { id: 255, parent: None, handle: NoDebug, data: vec![1, 2] };
+	assert_eq!(format!("{packet:?}"), "Packet { id: 0xff, handle: handle, data: <2 items> }");
+}
+
+#[test]
+fn formatter_gets_value_inside_some() {
+	let packet = Packet { id: 1, parent: Some(16), handle: NoDebug, data: vec![] };
+	assert_eq!(format!("{packet:?}"), "Packet { id: 0x1, parent: 0x10, handle: handle }");
+}
+
+#[test]
+fn formatter_on_tuple_field() {
+	assert_eq!(format!("{:?}", Flags(255, 1)), "Flags(0xff, 1)");
+}